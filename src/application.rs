@@ -54,6 +54,9 @@ pub enum Action {
     /// Create comments on the commits of a branch in this repository.
     CommentChanges(crate::CommentChanges),
 
+    /// Lint commit messages regarding the conventions of `comment-changes`.
+    CommitMsg(crate::CommitMsg),
+
     /// Complain about certain stylistic issues.
     Complain(crate::Complain),
 
@@ -124,6 +127,7 @@ impl Action {
                 file_to_edit.truncate(Box::new(buffer))
             }
             Self::CommentChanges(c) => c.main(),
+            Self::CommitMsg(c) => c.main(),
             Self::Complain(c) => c.main(),
             /*
             Self::GraphDescription { input_file } => {
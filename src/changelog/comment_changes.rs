@@ -17,27 +17,17 @@
 |                                                                              |
 \******************************************************************************/
 
+use super::Harvester;
 use crate::{FragmentExportFormat, ToMd, ToRon, ToRst, ToXml};
 use aeruginous_io::PathBufLikeAppendix;
 use git2::{Oid, Repository};
+use std::path::PathBuf;
 use sysexits::{ExitCode, Result};
 
 /// Create comments on the commits of a branch in this repository.
 #[derive(clap::Parser, Clone)]
 #[command(visible_aliases = ["changelog"])]
 pub struct CommentChanges {
-    /// Work with the commit messages' bodies instead of their summaries.
-    #[arg(long, short)]
-    body: bool,
-
-    /// Only these categories shall be used to generate comments.
-    #[arg(long, short)]
-    category: Vec<String>,
-
-    /// The delimiter to separate a category from the change description.
-    #[arg(long, short)]
-    delimiter: String,
-
     /// The count of commits to analyse, defaulting to infinity, if omitted.
     #[arg(long, short = 'n', visible_aliases = ["count"])]
     depth: Option<usize>,
@@ -51,13 +41,9 @@ pub struct CommentChanges {
     )]
     extension: FragmentExportFormat,
 
-    /// The default category to assign.
-    #[arg(long, short = 'C')]
-    fallback_category: Option<String>,
-
-    /// Whether to enforce the fragment creation.
-    #[arg(long, short = 'F')]
-    force: bool,
+    /// The settings on how to harvest the commit messages.
+    #[command(flatten)]
+    harvester: Harvester,
 
     /// The heading's level in the resulting fragment.
     #[arg(
@@ -69,10 +55,6 @@ pub struct CommentChanges {
     )]
     heading: u8,
 
-    /// Set categories Added, Changed, Deprecated, Fixed, Removed, and Security.
-    #[arg(long, short)]
-    keep_a_changelog: bool,
-
     /// The hyperlinks to add as comments.
     #[arg(long, short, visible_aliases = ["hyperlink"])]
     link: Vec<String>,
@@ -86,6 +68,10 @@ pub struct CommentChanges {
     )]
     output_directory: String,
 
+    /// The repository to analyse, defaulting to the one enclosing `.`.
+    #[arg(default_value = ".", long, short = 'r')]
+    repository: PathBuf,
+
     /// The position to stop at.
     #[arg(long, short = '@')]
     stop: Vec<String>,
@@ -128,17 +114,13 @@ impl CommentChanges {
     #[must_use]
     pub fn new(delimiter: String) -> Self {
         Self {
-            body: false,
-            category: Vec::new(),
-            delimiter,
             depth: None,
             extension: FragmentExportFormat::Rst,
-            fallback_category: None,
-            force: false,
+            harvester: Harvester::new(delimiter),
             heading: 3,
-            keep_a_changelog: false,
             link: Vec::new(),
             output_directory: ".".to_string(),
+            repository: PathBuf::from("."),
             stop: Vec::new(),
             stop_at: None,
             tag: None,
@@ -256,33 +238,6 @@ impl Logic {
         }
     }
 
-    fn harvest_message(&self, message: &str) -> Option<(String, String)> {
-        if message.is_empty() {
-            None
-        } else if let Some((category, change)) =
-            message.trim().split_once(&self.cli.delimiter)
-        {
-            let category = category.trim().to_string();
-            let change = change.trim().to_string();
-            let valid_category = self.categories.iter().any(|c| c == &category);
-
-            if self.categories.is_empty() || valid_category {
-                Some((category, change))
-            } else if !valid_category {
-                self.cli
-                    .fallback_category
-                    .as_ref()
-                    .map(|fallback| (fallback.to_string(), change))
-            } else {
-                None
-            }
-        } else {
-            self.cli.fallback_category.as_ref().map(|fallback| {
-                (fallback.to_string(), message.trim().to_string())
-            })
-        }
-    }
-
     fn main(&mut self) -> Result<()> {
         self.preprocess()?;
         self.query()?;
@@ -291,23 +246,7 @@ impl Logic {
 
     #[allow(deprecated)]
     fn preprocess(&mut self) -> Result<()> {
-        if self.cli.keep_a_changelog {
-            self.categories.append(
-                &mut [
-                    "Added",
-                    "Changed",
-                    "Deprecated",
-                    "Fixed",
-                    "Removed",
-                    "Security",
-                ]
-                .iter()
-                .map(ToString::to_string)
-                .collect::<Vec<String>>(),
-            );
-        }
-
-        self.categories.append(&mut self.cli.category.clone());
+        self.categories = self.cli.harvester.categories();
         self.fragment.reference(
             self.cli
                 .link
@@ -317,9 +256,11 @@ impl Logic {
                 .collect(),
         );
 
-        Repository::open(".").map_or_else(
+        let path = self.cli.repository.clone();
+
+        Repository::discover(&path).map_or_else(
             |_| {
-                eprintln!("This is not a Git repository.");
+                eprintln!("{} is not within a Git repository.", path.display());
                 Err(ExitCode::Usage)
             },
             |r| {
@@ -384,32 +325,13 @@ impl Logic {
                                 if let Ok(commit) = repository.find_commit(oid)
                                 {
                                     if let Some(message) = commit.message() {
-                                        let (summary, body) = message
-                                            .split_once('\n')
-                                            .unwrap_or((message, ""));
-
-                                        if let Some((category, change)) = self
-                                            .harvest_message(if self.cli.body {
-                                                body.trim()
-                                            } else {
-                                                summary.trim()
-                                            })
+                                        if let Ok((category, change)) = self
+                                            .cli
+                                            .harvester
+                                            .harvest(&self.categories, message)
                                         {
                                             self.fragment
                                                 .insert(&category, &change);
-                                        } else if self.cli.force {
-                                            if let Some((category, change)) =
-                                                self.harvest_message(
-                                                    if self.cli.body {
-                                                        summary.trim()
-                                                    } else {
-                                                        body.trim()
-                                                    },
-                                                )
-                                            {
-                                                self.fragment
-                                                    .insert(&category, &change);
-                                            }
                                         }
                                     }
                                 } else {
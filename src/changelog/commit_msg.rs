@@ -0,0 +1,184 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::Harvester;
+use crate::ceprintlns;
+use aeruginous_io::PathBufLikeReader;
+use git2::Repository;
+use std::path::PathBuf;
+use sysexits::{ExitCode, Result};
+
+/// Lint commit messages regarding the conventions of `comment-changes`.
+#[derive(clap::Parser, Clone)]
+#[command(visible_aliases = ["commitmsg", "lint-commit-msg"])]
+pub struct CommitMsg {
+    /// The settings on how to harvest the commit messages.
+    #[command(flatten)]
+    harvester: Harvester,
+
+    /// The file containing the commit message, as given to `commit-msg` hooks.
+    #[arg(long = "input", short, visible_aliases = ["file"])]
+    input_file: Option<PathBuf>,
+
+    /// The revision range to check, such as `main..HEAD`.
+    #[arg(required_unless_present = "input_file")]
+    range: Option<String>,
+
+    /// The repository to analyse, defaulting to the one enclosing `.`.
+    #[arg(default_value = ".", long, short = 'r')]
+    repository: PathBuf,
+}
+
+impl CommitMsg {
+    /// Process the input data.
+    ///
+    /// # Errors
+    ///
+    /// See [`sysexits::ExitCode`].
+    pub fn main(&self) -> Result<()> {
+        if self.process()? == 0 {
+            Ok(())
+        } else {
+            Err(ExitCode::DataErr)
+        }
+    }
+
+    /// Create a new instance.
+    #[must_use]
+    pub fn new(
+        delimiter: String,
+        input_file: Option<PathBuf>,
+        range: Option<String>,
+    ) -> Self {
+        Self {
+            harvester: Harvester::new(delimiter),
+            input_file,
+            range,
+            repository: PathBuf::from("."),
+        }
+    }
+
+    /// Count the commit messages which would not be harvested.
+    ///
+    /// # Errors
+    ///
+    /// See [`sysexits::ExitCode`].
+    pub fn process(&self) -> Result<usize> {
+        let categories = self.harvester.categories();
+        let mut rejections = 0;
+
+        if let Some(file) = &self.input_file {
+            if let Err(rejection) = self
+                .harvester
+                .harvest(&categories, &strip_comments(&file.read_loudly()?))
+            {
+                rejections += 1;
+
+                ceprintlns!(
+                    "Rejected"!Red,
+                    "{} as {rejection}.",
+                    file.display()
+                );
+            }
+        }
+
+        if let Some(range) = &self.range {
+            for (oid, message) in self.revisions(range)? {
+                if let Err(rejection) =
+                    self.harvester.harvest(&categories, &message)
+                {
+                    rejections += 1;
+
+                    ceprintlns!(
+                        "Rejected"!Red,
+                        "{:.7} ({}) as {rejection}.",
+                        oid.to_string(),
+                        message.lines().next().unwrap_or_default().trim()
+                    );
+                }
+            }
+        }
+
+        Ok(rejections)
+    }
+
+    fn revisions(&self, range: &str) -> Result<Vec<(git2::Oid, String)>> {
+        let repository =
+            Repository::discover(&self.repository).map_err(|_| {
+                eprintln!(
+                    "{} is not within a Git repository.",
+                    self.repository.display()
+                );
+                ExitCode::Usage
+            })?;
+        let mut revwalk = repository.revwalk().map_err(|error| {
+            eprintln!("{error}");
+            ExitCode::Unavailable
+        })?;
+        let specification = repository.revparse(range).map_err(|error| {
+            eprintln!("{error}");
+            ExitCode::Usage
+        })?;
+
+        if let Some(to) = specification.to() {
+            revwalk.push(to.id()).map_err(|_| ExitCode::Unavailable)?;
+
+            if let Some(from) = specification.from() {
+                revwalk.hide(from.id()).map_err(|_| ExitCode::Unavailable)?;
+            }
+        } else if let Some(from) = specification.from() {
+            revwalk.push(from.id()).map_err(|_| ExitCode::Unavailable)?;
+        }
+
+        let mut result = Vec::new();
+
+        for oid in revwalk {
+            let oid = oid.map_err(|_| {
+                eprintln!("Too few commits were fetched on checkout.");
+                ExitCode::Usage
+            })?;
+            let commit = repository.find_commit(oid).map_err(|_| {
+                eprintln!("Commit {oid} does not seem to exist.");
+                ExitCode::DataErr
+            })?;
+
+            result
+                .push((oid, commit.message().unwrap_or_default().to_string()));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Remove everything Git would strip from a commit message file.
+///
+/// Lines starting with `#` are comments.  Anything below the scissors line, as
+/// inserted by `git commit --verbose`, will be ignored, as well.
+fn strip_comments(message: &str) -> String {
+    message
+        .lines()
+        .take_while(|l| !l.starts_with("# ------------------------ >8"))
+        .filter(|l| !l.starts_with('#'))
+        .map(|l| l.to_string() + "\n")
+        .collect::<String>()
+        .trim_start()
+        .to_string()
+}
+
+/******************************************************************************/
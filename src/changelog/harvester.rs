@@ -0,0 +1,180 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+/// The settings on how to harvest changes from commit messages.
+#[derive(clap::Args, Clone)]
+pub struct Harvester {
+    /// Work with the commit messages' bodies instead of their summaries.
+    #[arg(long, short)]
+    body: bool,
+
+    /// Only these categories shall be used to generate comments.
+    #[arg(long, short)]
+    category: Vec<String>,
+
+    /// The delimiter to separate a category from the change description.
+    #[arg(long, short)]
+    delimiter: String,
+
+    /// The default category to assign.
+    #[arg(long, short = 'C')]
+    fallback_category: Option<String>,
+
+    /// Whether to enforce the fragment creation.
+    #[arg(long, short = 'F')]
+    force: bool,
+
+    /// Set categories Added, Changed, Deprecated, Fixed, Removed, and Security.
+    #[arg(long, short)]
+    keep_a_changelog: bool,
+}
+
+impl Harvester {
+    /// Collect the categories which are allowed to be harvested.
+    #[must_use]
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories = Vec::new();
+
+        if self.keep_a_changelog {
+            categories.extend(
+                [
+                    "Added",
+                    "Changed",
+                    "Deprecated",
+                    "Fixed",
+                    "Removed",
+                    "Security",
+                ]
+                .iter()
+                .map(ToString::to_string),
+            );
+        }
+
+        categories.extend(self.category.iter().cloned());
+        categories
+    }
+
+    /// Harvest the change from a whole commit message.
+    ///
+    /// The message will be split into its summary and its body.  The preferred
+    /// part will be harvested first.  In case of a failure, the other part will
+    /// be considered if the harvest is enforced.
+    ///
+    /// # Errors
+    ///
+    /// The reason why the preferred part could not be harvested.
+    pub fn harvest(
+        &self,
+        categories: &[String],
+        message: &str,
+    ) -> std::result::Result<(String, String), Rejection> {
+        let (summary, body) = message.split_once('\n').unwrap_or((message, ""));
+        let (preferred, other) = if self.body {
+            (body.trim(), summary.trim())
+        } else {
+            (summary.trim(), body.trim())
+        };
+
+        self.harvest_message(categories, preferred)
+            .or_else(|rejection| {
+                if self.force {
+                    self.harvest_message(categories, other)
+                        .map_err(|_| rejection)
+                } else {
+                    Err(rejection)
+                }
+            })
+    }
+
+    /// Harvest the change from a part of a commit message.
+    ///
+    /// # Errors
+    ///
+    /// The reason why this message part could not be harvested.
+    pub fn harvest_message(
+        &self,
+        categories: &[String],
+        message: &str,
+    ) -> std::result::Result<(String, String), Rejection> {
+        if message.is_empty() {
+            Err(Rejection::Empty)
+        } else if let Some((category, change)) =
+            message.trim().split_once(&self.delimiter)
+        {
+            let category = category.trim().to_string();
+            let change = change.trim().to_string();
+
+            if categories.is_empty() || categories.contains(&category) {
+                Ok((category, change))
+            } else {
+                self.fallback_category.as_ref().map_or(
+                    Err(Rejection::UnknownCategory(category)),
+                    |fallback| Ok((fallback.clone(), change)),
+                )
+            }
+        } else {
+            self.fallback_category.as_ref().map_or_else(
+                || Err(Rejection::MissingDelimiter(self.delimiter.clone())),
+                |fallback| Ok((fallback.clone(), message.trim().to_string())),
+            )
+        }
+    }
+
+    /// Create a new instance.
+    #[must_use]
+    pub const fn new(delimiter: String) -> Self {
+        Self {
+            body: false,
+            category: Vec::new(),
+            delimiter,
+            fallback_category: None,
+            force: false,
+            keep_a_changelog: false,
+        }
+    }
+}
+
+/// The reasons why a commit message cannot be harvested.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Rejection {
+    /// The message is empty.
+    Empty,
+
+    /// The message does not contain the delimiter.
+    MissingDelimiter(String),
+
+    /// The category is not allowed.
+    UnknownCategory(String),
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the message is empty"),
+            Self::MissingDelimiter(delimiter) => {
+                write!(f, "the delimiter `{delimiter}` is missing")
+            }
+            Self::UnknownCategory(category) => {
+                write!(f, "the category `{category}` is not allowed")
+            }
+        }
+    }
+}
+
+/******************************************************************************/
@@ -18,14 +18,19 @@
 \******************************************************************************/
 
 mod comment_changes;
+mod commit_msg;
 mod fragment;
+mod harvester;
 mod ronlog;
 
 pub use comment_changes::CommentChanges;
+pub use commit_msg::CommitMsg;
 pub use fragment::{ExportFormat as FragmentExportFormat, Fragment};
 pub use ronlog::{
     Action as RonlogAction, References as RonlogReferences, Ronlog,
     Section as RonlogSection,
 };
 
+use harvester::Harvester;
+
 /******************************************************************************/
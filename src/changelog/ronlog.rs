@@ -276,6 +276,19 @@ impl Ronlog {
         self.wrap().main()
     }
 
+    /// Read the sections of the given RONLOG.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::DataErr`]
+    /// - See [`aeruginous_io::PathBufLikeReader::read_loudly`].
+    pub fn sections<T>(path: T) -> Result<Vec<Section>>
+    where
+        PathBuf: From<T>,
+    {
+        Ok(Changelog::from_ron(&PathBuf::from(path).read_loudly()?)?.sections)
+    }
+
     fn wrap(&self) -> Logic {
         Logic {
             cli: self.clone(),
@@ -0,0 +1,127 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use aeruginous_io::{PathBufLikeReader, PathBufLikeTruncation};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+use sysexits::{ExitCode, Result};
+
+/// The known violations to tolerate.
+///
+/// A baseline is a JSON object mapping the files to their rules and these to
+/// the number of violations per fingerprint of the affected line's content.
+/// Hence, the violations are still recognised when their lines are moved.
+///
+/// ```json
+/// {
+///   "src/main.rs": {
+///     "ÆC-0003": {
+///       "a3f2c5e1d0b49876": 2
+///     }
+///   }
+/// }
+/// ```
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub struct Baseline {
+    findings: BTreeMap<String, BTreeMap<String, BTreeMap<String, usize>>>,
+}
+
+impl Baseline {
+    /// Adopt the violations of all files from another baseline which are not
+    /// contained in the given set of files.
+    pub fn adopt(&mut self, other: &Self, except: &BTreeSet<String>) {
+        for (file, rules) in &other.findings {
+            if !except.contains(file) {
+                self.findings.insert(file.clone(), rules.clone());
+            }
+        }
+    }
+
+    /// Record a violation of the given rule in the given line of a file.
+    pub fn insert(&mut self, file: &str, rule: &str, line: &str) {
+        *self
+            .findings
+            .entry(file.to_string())
+            .or_default()
+            .entry(rule.to_string())
+            .or_default()
+            .entry(fingerprint(line))
+            .or_default() += 1;
+    }
+
+    /// Read a baseline file.
+    ///
+    /// # Errors
+    ///
+    /// - [`aeruginous_io::PathBufLikeReader::read_loudly`]
+    /// - [`sysexits::ExitCode::DataErr`]
+    pub fn load(path: &Path) -> Result<Self> {
+        serde_json::from_str(&path.read_loudly()?).map_err(|e| {
+            eprintln!("{}:  {e}", path.display());
+            ExitCode::DataErr
+        })
+    }
+
+    /// Write this baseline to the given file.
+    ///
+    /// # Errors
+    ///
+    /// - [`aeruginous_io::PathBufLikeTruncation::truncate_loudly`]
+    /// - [`sysexits::ExitCode::DataErr`]
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut data = serde_json::to_string_pretty(self)
+            .map_err(|_| ExitCode::DataErr)?;
+
+        data.push('\n');
+        data.truncate_loudly(path)
+    }
+
+    /// Remove a known violation of the given rule in the given line of a file.
+    ///
+    /// The return value states whether the violation was known.
+    pub fn take(&mut self, file: &str, rule: &str, line: &str) -> bool {
+        self.findings
+            .get_mut(file)
+            .and_then(|rules| rules.get_mut(rule))
+            .and_then(|lines| lines.get_mut(&fingerprint(line)))
+            .is_some_and(|count| {
+                if *count == 0 {
+                    false
+                } else {
+                    *count -= 1;
+                    true
+                }
+            })
+    }
+}
+
+/// Compute the 64 bit FNV-1a hash of the given line as hexadecimal number.
+fn fingerprint(line: &str) -> String {
+    format!(
+        "{:016x}",
+        line.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    )
+}
+
+/******************************************************************************/
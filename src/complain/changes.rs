@@ -0,0 +1,156 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use git2::{Delta, DiffOptions, Repository};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use sysexits::{ExitCode, Result};
+
+/// The lines changed in the working tree since a certain revision.
+pub struct Changes {
+    files: HashMap<PathBuf, Vec<(usize, usize)>>,
+    root: PathBuf,
+}
+
+impl Changes {
+    /// Check whether the given file was changed.
+    pub fn contains(&self, file: &Path) -> bool {
+        self.lines(file).is_some()
+    }
+
+    /// Check whether the given line of a file was changed.
+    pub fn contains_line(&self, file: &Path, line: usize) -> bool {
+        self.lines(file).is_some_and(|ranges| {
+            ranges
+                .iter()
+                .any(|(first, last)| (*first..=*last).contains(&line))
+        })
+    }
+
+    /// Compare the working tree of the repository enclosing `.` to the given
+    /// revision.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::Unavailable`]
+    /// - [`sysexits::ExitCode::Usage`]
+    pub fn new(revision: &str) -> Result<Self> {
+        let Ok(repository) = Repository::discover(".") else {
+            eprintln!(". is not within a Git repository.");
+            return Err(ExitCode::Usage);
+        };
+        let Some(root) = repository.workdir() else {
+            eprintln!("The repository does not have a working tree.");
+            return Err(ExitCode::Usage);
+        };
+        let tree = repository
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_tree())
+            .map_err(|e| {
+                eprintln!("{e}");
+                ExitCode::Usage
+            })?;
+        let files =
+            RefCell::new(HashMap::<PathBuf, Vec<(usize, usize)>>::new());
+
+        repository
+            .diff_tree_to_workdir_with_index(
+                Some(&tree),
+                Some(
+                    DiffOptions::new()
+                        .context_lines(0)
+                        .include_untracked(true)
+                        .recurse_untracked_dirs(true)
+                        .show_untracked_content(true),
+                ),
+            )
+            .and_then(|diff| {
+                diff.foreach(
+                    &mut |delta, _| {
+                        if delta.status() != Delta::Deleted {
+                            if let Some(path) = delta.new_file().path() {
+                                files
+                                    .borrow_mut()
+                                    .entry(path.to_path_buf())
+                                    .or_default();
+                            }
+                        }
+
+                        true
+                    },
+                    None,
+                    Some(&mut |delta, hunk| {
+                        if let Some(path) = delta.new_file().path() {
+                            if hunk.new_lines() > 0 {
+                                let first = hunk.new_start() as usize;
+
+                                files
+                                    .borrow_mut()
+                                    .entry(path.to_path_buf())
+                                    .or_default()
+                                    .push((
+                                        first,
+                                        first + hunk.new_lines() as usize - 1,
+                                    ));
+                            }
+                        }
+
+                        true
+                    }),
+                    None,
+                )
+            })
+            .map_err(|e| {
+                eprintln!("{e}");
+                ExitCode::Unavailable
+            })?;
+
+        Ok(Self {
+            files: files.into_inner(),
+            root: root.canonicalize()?,
+        })
+    }
+
+    /// Look up the changed lines of the given file.
+    ///
+    /// Files which do not exist on disk, such as the name given to the
+    /// contents of [`std::io::Stdin`], are looked up by their path relative to
+    /// the repository root instead.
+    fn lines(&self, file: &Path) -> Option<&Vec<(usize, usize)>> {
+        let relative = |path: &Path| {
+            path.strip_prefix(&self.root).ok().map(Path::to_path_buf)
+        };
+
+        file.canonicalize()
+            .ok()
+            .and_then(|path| relative(&path))
+            .or_else(|| {
+                std::path::absolute(file)
+                    .ok()
+                    .and_then(|path| relative(&path))
+            })
+            .or_else(|| file.is_relative().then(|| file.to_path_buf()))
+            .and_then(|path| self.files.get(&path))
+    }
+}
+
+/******************************************************************************/
@@ -0,0 +1,368 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{
+    header::{Header, Style, Template},
+    rules::Custom,
+    IndentationUnit, Severity,
+};
+use aeruginous_io::PathBufLikeReader;
+use anstyle::AnsiColor;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
+use sysexits::{ExitCode, Result};
+
+/// The settings of a configuration file for `complain`.
+///
+/// A configuration file is written in TOML and consists of sections, each of
+/// which applies its settings to all files matching at least one of its globs.
+/// Globs are relative to the directory of the configuration file and match the
+/// file name in any directory if they do not contain a slash.  Later sections
+/// take precedence over earlier ones.
+///
+/// Furthermore, custom rules can be defined by regular expressions.  Each match
+/// within a line is reported with the given identifier, message, severity, and
+/// colour, optionally restricted to the files matching the given globs.
+///
+/// ```toml
+/// [[section]]
+/// files = ["Makefile", "*.mk"]
+/// indent_by = "tabs"
+///
+/// [[section]]
+/// files = ["*.py"]
+/// line_width = 100
+///
+/// [[rule]]
+/// colour = "green"
+/// files = ["*.rs"]
+/// id = "no-dbg"
+/// message = "Debugging macro left over."
+/// regex = 'dbg!\('
+/// severity = "warning"
+/// ```
+///
+/// Finally, a `[header]` declares the license header the files have to start
+/// with, see [`Header`].
+#[derive(Default)]
+pub struct Config {
+    header: Option<(Option<GlobSet>, Header)>,
+    root: PathBuf,
+    rules: Vec<(Option<GlobSet>, Custom)>,
+    sections: Vec<(GlobSet, Overrides)>,
+}
+
+impl Config {
+    /// Read a configuration file.
+    ///
+    /// # Errors
+    ///
+    /// - [`aeruginous_io::PathBufLikeReader::read_loudly`]
+    /// - [`sysexits::ExitCode::Config`]
+    pub fn load(path: &Path) -> Result<Self> {
+        let file: File = toml::from_str(&path.read_loudly()?).map_err(|e| {
+            eprintln!("{}:  {e}", path.display());
+            ExitCode::Config
+        })?;
+        let mut rules = Vec::new();
+        let mut sections = Vec::new();
+
+        for rule in file.rule {
+            rules.push(rule.compile()?);
+        }
+
+        for section in file.section {
+            sections.push((globs(&section.files)?, section.overrides));
+        }
+
+        Ok(Self {
+            header: file
+                .header
+                .map(|header| {
+                    Ok::<_, ExitCode>((
+                        header.files.as_deref().map(globs).transpose()?,
+                        Header::new(
+                            &header.template,
+                            &header.holder,
+                            header.styles,
+                            header.current_year,
+                        )?,
+                    ))
+                })
+                .transpose()?,
+            root: std::path::absolute(path.parent().unwrap_or(path))?,
+            rules,
+            sections,
+        })
+    }
+
+    /// Select the license header the given file has to start with, if any.
+    pub fn header(&self, file: &Path) -> Option<&Template> {
+        let (globs, header) = self.header.as_ref()?;
+
+        if globs
+            .as_ref()
+            .is_none_or(|globs| globs.is_match(self.relative(file)))
+        {
+            header.template(file)
+        } else {
+            None
+        }
+    }
+
+    /// Collect the settings which apply to the given file, in order.
+    pub fn overrides(&self, file: &Path) -> impl Iterator<Item = &Overrides> {
+        let file = self.relative(file);
+
+        self.sections
+            .iter()
+            .filter(move |(globs, _)| globs.is_match(&file))
+            .map(|(_, overrides)| overrides)
+    }
+
+    /// Collect the custom rules which apply to the given file, in order.
+    pub fn rules(&self, file: &Path) -> impl Iterator<Item = &Custom> {
+        let file = self.relative(file);
+
+        self.rules
+            .iter()
+            .filter(move |(globs, _)| {
+                globs.as_ref().is_none_or(|globs| globs.is_match(&file))
+            })
+            .map(|(_, rule)| rule)
+    }
+
+    fn relative(&self, file: &Path) -> PathBuf {
+        std::path::absolute(file).map_or_else(
+            |_| normalise(file),
+            |path| {
+                path.strip_prefix(&self.root)
+                    .map_or_else(|_| normalise(file), normalise)
+            },
+        )
+    }
+}
+
+/// The colours to highlight custom rules with.
+#[derive(Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Colour {
+    Green,
+    Red,
+    #[default]
+    Yellow,
+}
+
+impl From<Colour> for AnsiColor {
+    fn from(colour: Colour) -> Self {
+        match colour {
+            Colour::Green => Self::Green,
+            Colour::Red => Self::Red,
+            Colour::Yellow => Self::Yellow,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    header: Option<HeaderDefinition>,
+
+    #[serde(default)]
+    rule: Vec<RuleDefinition>,
+
+    #[serde(default)]
+    section: Vec<Section>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HeaderDefinition {
+    #[serde(default)]
+    current_year: bool,
+    files: Option<Vec<String>>,
+    #[serde(default)]
+    holder: String,
+    #[serde(default)]
+    styles: HashMap<String, Style>,
+    template: String,
+}
+
+/// The settings to override for certain files.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Overrides {
+    /// Whether to ignore UTF-8 BOMs at the beginning of files.
+    pub ignore_byte_order_mark: Option<bool>,
+
+    /// Whether to ignore CRLFs.
+    pub ignore_carriage_return_line_feeds: Option<bool>,
+
+    /// Whether to ignore too many consecutive blank lines.
+    pub ignore_consecutive_blank_lines: Option<bool>,
+
+    /// Whether to ignore invisible and bidirectional control characters.
+    pub ignore_invisible_characters: Option<bool>,
+
+    /// Whether to ignore missing or outdated license headers.
+    pub ignore_license_header: Option<bool>,
+
+    /// Whether to ignore line width issues.
+    pub ignore_line_width_issues: Option<bool>,
+
+    /// Whether to ignore Markdown headings without surrounding blank lines.
+    pub ignore_markdown_heading_spacing: Option<bool>,
+
+    /// Whether to ignore leftover merge conflict markers.
+    pub ignore_merge_conflict_markers: Option<bool>,
+
+    /// Whether to ignore if a file should not be terminated by a line feed.
+    pub ignore_missing_final_line_feed: Option<bool>,
+
+    /// Whether to ignore the usage of mixed indentation units.
+    pub ignore_mixed_indentation: Option<bool>,
+
+    /// Whether to ignore the usage of both CRLFs and LFs within one file.
+    pub ignore_mixed_line_endings: Option<bool>,
+
+    /// Whether to ignore tabs within lines.
+    pub ignore_tabs_within_lines: Option<bool>,
+
+    /// Whether to ignore blank lines at the end of files.
+    pub ignore_trailing_blank_lines: Option<bool>,
+
+    /// Whether to ignore TWS.
+    pub ignore_trailing_white_space_characters: Option<bool>,
+
+    /// Whether to ignore the usage of wrong indentation units.
+    pub ignore_wrong_indentation: Option<bool>,
+
+    /// Whether to ignore YAML files indented by tabs.
+    pub ignore_yaml_tab_indentation: Option<bool>,
+
+    /// The indentation unit.
+    pub indent_by: Option<IndentationUnit>,
+
+    /// The maximum line width to check for.
+    pub line_width: Option<usize>,
+
+    /// The maximum count of consecutive blank lines.
+    pub max_blank_lines: Option<usize>,
+
+    /// The tab stop width.
+    pub tab_width: Option<usize>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDefinition {
+    #[serde(default)]
+    colour: Colour,
+    files: Option<Vec<String>>,
+    id: String,
+    message: String,
+    regex: String,
+    #[serde(default)]
+    severity: Severity,
+}
+
+impl RuleDefinition {
+    fn compile(self) -> Result<(Option<GlobSet>, Custom)> {
+        if self.id.is_empty()
+            || self.id.starts_with("ÆC-")
+            || self.id.contains("::")
+            || self.id.contains(|c: char| c == ']' || c.is_whitespace())
+        {
+            eprintln!("{:?} is not a valid identifier for a rule.", self.id);
+            return Err(ExitCode::Config);
+        }
+
+        let regex = regex::Regex::new(&self.regex).map_err(|e| {
+            eprintln!("{}:  {e}", self.id);
+            ExitCode::Config
+        })?;
+
+        Ok((
+            self.files.as_deref().map(globs).transpose()?,
+            Custom {
+                colour: self.colour.into(),
+                id: self.id,
+                message: self.message,
+                regex,
+                severity: self.severity,
+            },
+        ))
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Section {
+    files: Vec<String>,
+
+    #[serde(flatten)]
+    overrides: Overrides,
+}
+
+/// Compile the given globs.
+///
+/// Globs without a slash match the file name in any directory.
+///
+/// # Errors
+///
+/// - [`sysexits::ExitCode::Config`]
+pub fn globs<T: AsRef<str>>(patterns: &[T]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        let pattern = pattern.as_ref();
+        let pattern = if pattern.contains('/') {
+            pattern.trim_start_matches("./").trim_start_matches('/')
+        } else {
+            &format!("**/{pattern}")
+        };
+
+        builder.add(
+            GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| {
+                    eprintln!("{e}");
+                    ExitCode::Config
+                })?,
+        );
+    }
+
+    builder.build().map_err(|e| {
+        eprintln!("{e}");
+        ExitCode::Config
+    })
+}
+
+/// Remove any `.` components from the given path.
+pub fn normalise(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| c != &Component::CurDir)
+        .collect()
+}
+
+/******************************************************************************/
@@ -0,0 +1,150 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use anstyle::AnsiColor;
+use std::path::PathBuf;
+
+/// A single violation of a lint.
+///
+/// Each violation found by [`crate::Complain`] is described by the file and the
+/// position it occurs at, the identifier of the violated rule, its severity, as
+/// well as a message explaining the issue.  The colour indicates the effort to
+/// fix the violation:  green ones are easy to fix, yellow ones are of moderate
+/// difficulty, and red ones require major changes.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Diagnostic {
+    /// The file the violation was found in.
+    file: PathBuf,
+
+    /// The line of the violation, counting from 1.
+    line: usize,
+
+    /// The column of the violation, counting from 1.
+    column: usize,
+
+    /// The identifier of the violated rule.
+    rule: String,
+
+    /// The severity of the violation.
+    severity: Severity,
+
+    /// The description of the violation.
+    message: String,
+
+    /// The colour to highlight the rule with, indicating the effort to fix it.
+    #[serde(skip)]
+    colour: AnsiColor,
+
+    /// The count of characters affected by the violation.
+    #[serde(skip)]
+    length: usize,
+}
+
+impl Diagnostic {
+    crate::getters!(@fn @cp
+        line: usize,
+        column: usize,
+        severity: Severity,
+        colour: AnsiColor,
+        length: usize
+    );
+
+    crate::getters!(@fn @ref
+        file: PathBuf,
+        rule: String,
+        message: String
+    );
+
+    /// Create a new instance.
+    #[must_use]
+    pub const fn new(
+        file: PathBuf,
+        line: usize,
+        column: usize,
+        rule: String,
+        severity: Severity,
+        message: String,
+        colour: AnsiColor,
+    ) -> Self {
+        Self {
+            file,
+            line,
+            column,
+            rule,
+            severity,
+            message,
+            colour,
+            length: 1,
+        }
+    }
+
+    /// Set the count of characters affected by the violation.
+    #[must_use]
+    pub const fn with_length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    /// Format this instance as `<file>:<line>:<column>: <rule> <message>`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {} {}",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.rule,
+            self.message
+        )
+    }
+}
+
+/// The severity of a [`Diagnostic`].
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The violation needs to be fixed.
+    #[default]
+    Error,
+
+    /// The violation should be fixed.
+    Warning,
+
+    /// The violation is worth a notice.
+    Note,
+}
+
+crate::enum_trait!(Severity {
+    Error <-> "error",
+    Warning <-> "warning",
+    Note <-> "note"
+});
+
+/******************************************************************************/
@@ -0,0 +1,179 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{config::Overrides, IndentationUnit};
+use aeruginous_io::PathBufLikeReader;
+use globset::{GlobBuilder, GlobMatcher};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use sysexits::Result;
+
+/// The `.editorconfig` files known so far.
+///
+/// The settings are looked up in all `.editorconfig` files from the directory
+/// of the file in question up to the file system root or the first file which
+/// declares `root = true`.  Closer files take precedence over the ones further
+/// up in the hierarchy, just like later sections over earlier ones.
+#[derive(Default)]
+pub struct EditorConfig {
+    cache: HashMap<PathBuf, Option<Parsed>>,
+}
+
+impl EditorConfig {
+    /// Determine the settings for the given file.
+    ///
+    /// # Errors
+    ///
+    /// See [`aeruginous_io::PathBufLikeReader::read_loudly`].
+    pub fn overrides(&mut self, file: &Path) -> Result<Overrides> {
+        let file = std::path::absolute(file)?;
+        let mut hierarchy = Vec::new();
+
+        for directory in file.ancestors().skip(1) {
+            if !self.cache.contains_key(directory) {
+                let path = directory.join(".editorconfig");
+                let parsed = if path.is_file() {
+                    Some(Parsed::new(&path.read_loudly()?))
+                } else {
+                    None
+                };
+
+                self.cache.insert(directory.to_path_buf(), parsed);
+            }
+
+            hierarchy.push(directory);
+
+            if self.cache[directory].as_ref().is_some_and(|p| p.root) {
+                break;
+            }
+        }
+
+        let mut properties = HashMap::new();
+
+        for directory in hierarchy.into_iter().rev() {
+            if let (Some(parsed), Ok(relative)) =
+                (&self.cache[directory], file.strip_prefix(directory))
+            {
+                for (glob, section) in &parsed.sections {
+                    if glob.is_match(relative) {
+                        for (key, value) in section {
+                            properties.insert(key.as_str(), value.as_str());
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut result = Overrides::default();
+
+        for (key, value) in &properties {
+            apply(&mut result, key, value);
+        }
+
+        result.tab_width = properties
+            .get("tab_width")
+            .or_else(|| properties.get("indent_size"))
+            .and_then(|width| width.parse().ok());
+
+        Ok(result)
+    }
+}
+
+struct Parsed {
+    root: bool,
+    sections: Vec<(GlobMatcher, Vec<(String, String)>)>,
+}
+
+impl Parsed {
+    fn new(data: &str) -> Self {
+        let mut root = false;
+        let mut sections = Vec::new();
+        let mut current: Option<(GlobMatcher, Vec<(String, String)>)> = None;
+
+        for line in data.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+
+            if let Some(pattern) =
+                line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+            {
+                sections.extend(current.take());
+
+                let pattern = if pattern.contains('/') {
+                    pattern.trim_start_matches('/').to_string()
+                } else {
+                    format!("**/{pattern}")
+                };
+
+                current = GlobBuilder::new(&pattern)
+                    .literal_separator(true)
+                    .build()
+                    .ok()
+                    .map(|glob| (glob.compile_matcher(), Vec::new()));
+            } else if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim().to_lowercase();
+                let value = value.trim().to_lowercase();
+
+                if let Some((_, properties)) = &mut current {
+                    properties.push((key, value));
+                } else if key == "root" {
+                    root = value == "true";
+                }
+            }
+        }
+
+        sections.extend(current);
+
+        Self { root, sections }
+    }
+}
+
+fn apply(overrides: &mut Overrides, key: &str, value: &str) {
+    match (key, value) {
+        ("end_of_line", "cr" | "crlf") => {
+            overrides.ignore_carriage_return_line_feeds = Some(true);
+        }
+        ("indent_style", "space") => {
+            overrides.indent_by = Some(IndentationUnit::Spaces);
+        }
+        ("indent_style", "tab") => {
+            overrides.indent_by = Some(IndentationUnit::Tabs);
+        }
+        ("insert_final_newline", "false") => {
+            overrides.ignore_missing_final_line_feed = Some(true);
+        }
+        ("max_line_length", "off") => {
+            overrides.ignore_line_width_issues = Some(true);
+        }
+        ("max_line_length", _) => {
+            if let Ok(width) = value.parse() {
+                overrides.line_width = Some(width);
+            }
+        }
+        ("trim_trailing_whitespace", "false") => {
+            overrides.ignore_trailing_white_space_characters = Some(true);
+        }
+        _ => {}
+    }
+}
+
+/******************************************************************************/
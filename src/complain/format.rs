@@ -0,0 +1,337 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{rules, Diagnostic, Severity};
+use crate::{AppendAsLine, ColourMessage};
+use anstyle::AnsiColor;
+use std::{io::Write, path::Path};
+use sysexits::{ExitCode, Result};
+
+/// The supported output formats of `complain`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// Checkstyle XML.
+    Checkstyle,
+
+    /// GitHub Actions workflow commands to annotate the code.
+    Github,
+
+    /// Coloured messages and a summary per file on [`std::io::Stderr`].
+    #[default]
+    Human,
+
+    /// A JSON array of all violations.
+    Json,
+
+    /// Like [`Self::Human`] but with the affected source lines, in the style
+    /// of `rustc`.
+    Rich,
+
+    /// The Static Analysis Results Interchange Format (SARIF) 2.1.0.
+    Sarif,
+}
+
+crate::enum_trait!(OutputFormat {
+    Checkstyle <-> "checkstyle",
+    Github <-> "github",
+    Human <-> "human",
+    Json <-> "json",
+    Rich <-> "rich",
+    Sarif <-> "sarif"
+});
+
+impl OutputFormat {
+    /// Render the given diagnostics using this format.
+    ///
+    /// The human-readable formats are written while the files are processed
+    /// such that they will not be rendered by this method.  See
+    /// [`Self::render_file`].
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::DataErr`]
+    pub fn render(self, diagnostics: &[Diagnostic]) -> Result<String> {
+        match self {
+            Self::Checkstyle => Ok(checkstyle(diagnostics)),
+            Self::Github => Ok(github(diagnostics)),
+            Self::Human | Self::Rich => Ok(String::new()),
+            Self::Json => json(diagnostics),
+            Self::Sarif => json(&sarif(diagnostics)),
+        }
+    }
+
+    /// Render the diagnostics of a single file once it was processed.
+    ///
+    /// Only the human-readable formats are rendered by this method.  Each
+    /// violation is reported with the identifier of its rule being highlighted
+    /// in the violation's colour.  The rich format adds the position and the
+    /// affected line of the file's content `data`, marking the violation by
+    /// carets and trailing white space by visible characters.  Tabs are
+    /// expanded to the tab stops of the given `tab_width`.  Afterwards, a
+    /// summary consisting of an ASCII art crab, the number of violations, and
+    /// the file name is written, in case of violations or if `verbose` is set.
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::ColourMessage`].
+    pub fn render_file(
+        self,
+        file: &Path,
+        data: &str,
+        diagnostics: &[Diagnostic],
+        tab_width: usize,
+        verbose: bool,
+        stream: &mut dyn Write,
+    ) -> Result<()> {
+        if self == Self::Human || self == Self::Rich {
+            if self == Self::Rich {
+                rich(file, data, diagnostics, tab_width.max(1), stream)?;
+            } else {
+                for d in diagnostics {
+                    d.rule().colour_message(d.colour(), stream)?;
+                    writeln!(stream, " {}", d.message())?;
+                }
+            }
+
+            if verbose || !diagnostics.is_empty() {
+                "ˇ;{\"};ˇ".colour_message(AnsiColor::Blue, stream)?;
+                writeln!(stream, " {} {}", diagnostics.len(), file.display())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn rich(
+    file: &Path,
+    data: &str,
+    diagnostics: &[Diagnostic],
+    tab_width: usize,
+    stream: &mut dyn Write,
+) -> Result<()> {
+    let lines = data.lines().collect::<Vec<_>>();
+    let gutter = diagnostics
+        .iter()
+        .map(|d| d.line().to_string().len())
+        .max()
+        .unwrap_or_default();
+    let blank = " ".repeat(gutter);
+
+    for d in diagnostics {
+        format!("{}[{}]", d.severity(), d.rule())
+            .colour_message(d.colour(), stream)?;
+        writeln!(stream, ": {}", d.message())?;
+        write!(stream, "{blank}")?;
+        "-->".colour_message(AnsiColor::Blue, stream)?;
+        writeln!(stream, " {}:{}:{}", file.display(), d.line(), d.column())?;
+
+        if let Some(line) = lines.get(d.line() - 1) {
+            let (text, offset, width) =
+                snippet(line, d.column(), d.length(), tab_width);
+
+            format!("{blank} |").colour_message(AnsiColor::Blue, stream)?;
+            writeln!(stream)?;
+            format!("{:>gutter$} |", d.line())
+                .colour_message(AnsiColor::Blue, stream)?;
+            writeln!(stream, " {text}")?;
+            format!("{blank} |").colour_message(AnsiColor::Blue, stream)?;
+            write!(stream, " {}", " ".repeat(offset))?;
+            "^".repeat(width).colour_message(d.colour(), stream)?;
+            writeln!(stream)?;
+        }
+
+        writeln!(stream)?;
+    }
+
+    Ok(())
+}
+
+/// Prepare a line for display and locate the given span within it.
+///
+/// Tabs are expanded to the tab stops of the given width and trailing white
+/// space characters are made visible.  The span is returned as offset and width
+/// in display columns.
+fn snippet(
+    line: &str,
+    column: usize,
+    length: usize,
+    tab_width: usize,
+) -> (String, usize, usize) {
+    use unicode_width::UnicodeWidthStr;
+
+    let content = line.trim_end().chars().count();
+    let mut offset = None;
+    let mut text = String::new();
+    let mut width = None;
+
+    for (i, c) in (1..).zip(line.chars()) {
+        if i == column {
+            offset = Some(text.width());
+        }
+
+        if i == column + length {
+            width = Some(text.width());
+        }
+
+        match c {
+            '\t' if i > content => text.push('→'),
+            '\t' => {
+                let start = text.width();
+
+                text.push_str(
+                    &" ".repeat(rules::advance(start, c, tab_width) - start),
+                );
+            }
+            _ if i > content => text.push('·'),
+            _ => text.push(c),
+        }
+    }
+
+    let offset = offset.unwrap_or_else(|| text.width());
+    let width = width.unwrap_or_else(|| text.width()).saturating_sub(offset);
+
+    (text, offset, width.max(1))
+}
+
+fn checkstyle(diagnostics: &[Diagnostic]) -> String {
+    use quick_xml::escape::escape;
+
+    let mut result = String::new();
+
+    result.append_as_line("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    result.append_as_line("<checkstyle version=\"4.3\">");
+
+    for file in diagnostics.chunk_by(|a, b| a.file() == b.file()) {
+        result.append_as_line(format!(
+            "  <file name=\"{}\">",
+            escape(file[0].file().display().to_string())
+        ));
+
+        for d in file {
+            result.append_as_line(format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" \
+                 message=\"{}\" source=\"{}\"/>",
+                d.line(),
+                d.column(),
+                match d.severity() {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Note => "info",
+                },
+                escape(d.message()),
+                escape(d.rule())
+            ));
+        }
+
+        result.append_as_line("  </file>");
+    }
+
+    result.append_as_line("</checkstyle>");
+    result
+}
+
+fn github(diagnostics: &[Diagnostic]) -> String {
+    let data = |s: &str| {
+        s.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    };
+    let property = |s: &str| data(s).replace(':', "%3A").replace(',', "%2C");
+    let mut result = String::new();
+
+    for d in diagnostics {
+        result.append_as_line(format!(
+            "::{} file={},line={},col={},title={}::{}",
+            match d.severity() {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note => "notice",
+            },
+            property(&d.file().display().to_string()),
+            d.line(),
+            d.column(),
+            property(d.rule()),
+            data(d.message())
+        ));
+    }
+
+    result
+}
+
+fn json<T: serde::Serialize + ?Sized>(value: &T) -> Result<String> {
+    serde_json::to_string_pretty(value).map_or(
+        Err(ExitCode::DataErr),
+        |mut s| {
+            s.push('\n');
+            Ok(s)
+        },
+    )
+}
+
+fn sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let mut rules = diagnostics
+        .iter()
+        .map(|d| d.rule().clone())
+        .collect::<Vec<String>>();
+
+    rules.sort();
+    rules.dedup();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": crate::NAME,
+                    "informationUri":
+                        "https://github.com/kevinmatthes/aeruginous-rs",
+                    "version": crate::VERSION.trim_start_matches('v'),
+                    "rules": rules
+                        .iter()
+                        .map(|r| serde_json::json!({ "id": r }))
+                        .collect::<Vec<serde_json::Value>>(),
+                }
+            },
+            "results": diagnostics
+                .iter()
+                .map(|d| serde_json::json!({
+                    "ruleId": d.rule(),
+                    "level": d.severity().to_string(),
+                    "message": { "text": d.message() },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": d.file().display().to_string()
+                                    .replace('\\', "/"),
+                            },
+                            "region": {
+                                "startLine": d.line(),
+                                "startColumn": d.column(),
+                            }
+                        }
+                    }]
+                }))
+                .collect::<Vec<serde_json::Value>>(),
+        }]
+    })
+}
+
+/******************************************************************************/
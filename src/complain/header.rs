@@ -0,0 +1,424 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use regex::Regex;
+use std::{collections::HashMap, path::Path};
+use sysexits::{ExitCode, Result};
+use unicode_width::UnicodeWidthStr;
+
+/// The placeholder for the copyright holder.
+const HOLDER: &str = "{holder}";
+
+/// The placeholder for the copyright year range.
+const YEAR: &str = "{year}";
+
+/// The pattern to match a copyright year range with.
+const YEARS: &str = r"(\d{4})(?:-(\d{4}))?";
+
+/// The license header every matching file has to start with.
+///
+/// The header is rendered from a template with the placeholders `{holder}` and
+/// `{year}` in the comment style assigned to the file's extension.  A style
+/// consists of an optional first and last line enclosing the template, the
+/// prefix and suffix of each line, and the width to pad the lines to before
+/// appending the suffix.
+///
+/// ```toml
+/// [header]
+/// files = ["*.rs"]
+/// holder = "Jane Doe"
+/// template = """
+/// Copyright (C) {year} {holder}
+///
+/// SPDX-License-Identifier: MIT
+/// """
+///
+/// [header.styles.rs]
+/// first = "/*"
+/// last = " */"
+/// prefix = " * "
+/// ```
+pub struct Header {
+    styles: HashMap<String, usize>,
+    templates: Vec<Template>,
+}
+
+impl Header {
+    /// Compile the template for each comment style.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::Config`]
+    pub fn new(
+        template: &str,
+        holder: &str,
+        styles: HashMap<String, Style>,
+        current_year: bool,
+    ) -> Result<Self> {
+        let this_year = chrono::Datelike::year(&chrono::Local::now());
+        let mut result = Self {
+            styles: HashMap::new(),
+            templates: Vec::new(),
+        };
+        let mut known = Vec::<Style>::new();
+
+        if template.trim().is_empty() {
+            eprintln!("The license header template must not be empty.");
+            return Err(ExitCode::Config);
+        }
+
+        for (extensions, style) in DEFAULT_STYLES {
+            let style = Style {
+                prefix: (*style).to_string(),
+                ..Style::default()
+            };
+
+            for extension in *extensions {
+                result.styles.insert((*extension).to_string(), known.len());
+            }
+
+            known.push(style);
+        }
+
+        for (extension, style) in styles {
+            let index =
+                known.iter().position(|s| *s == style).unwrap_or_else(|| {
+                    known.push(style);
+                    known.len() - 1
+                });
+
+            result.styles.insert(extension, index);
+        }
+
+        for style in known {
+            result.templates.push(Template::new(
+                template,
+                holder,
+                style,
+                current_year,
+                this_year,
+            )?);
+        }
+
+        Ok(result)
+    }
+
+    /// Select the template for the given file by its extension.
+    pub fn template(&self, file: &Path) -> Option<&Template> {
+        file.extension()
+            .and_then(|extension| {
+                self.styles.get(&*extension.to_string_lossy())
+            })
+            .map(|&index| &self.templates[index])
+    }
+}
+
+/// The comment style to render a license header in.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Style {
+    /// The line to open the comment with.
+    #[serde(default)]
+    pub first: Option<String>,
+
+    /// The line to close the comment with.
+    #[serde(default)]
+    pub last: Option<String>,
+
+    /// The text to start each line with.
+    #[serde(default)]
+    pub prefix: String,
+
+    /// The text to end each line with.
+    #[serde(default)]
+    pub suffix: String,
+
+    /// The width to pad each line to before appending the suffix.
+    #[serde(default)]
+    pub width: Option<usize>,
+}
+
+impl Style {
+    fn render(&self, content: &str) -> String {
+        let mut line = format!("{}{content}", self.prefix);
+
+        if !self.suffix.is_empty() {
+            if let Some(width) = self.width {
+                let target = width.saturating_sub(self.suffix.width());
+
+                while line.width() < target {
+                    line.push(' ');
+                }
+            }
+
+            line.push_str(&self.suffix);
+        }
+
+        line.trim_end().to_string()
+    }
+}
+
+/// A license header rendered in a certain comment style.
+#[derive(Clone, Debug)]
+pub struct Template {
+    contents: Vec<(String, bool)>,
+    current_year: bool,
+    patterns: Vec<Regex>,
+    style: Style,
+    this_year: i32,
+}
+
+impl Template {
+    fn new(
+        template: &str,
+        holder: &str,
+        style: Style,
+        current_year: bool,
+        this_year: i32,
+    ) -> Result<Self> {
+        let mut contents = Vec::new();
+
+        if let Some(first) = &style.first {
+            contents.push((first.replace(HOLDER, holder), false));
+        }
+
+        for line in template.lines() {
+            contents.push((line.replace(HOLDER, holder), true));
+        }
+
+        if let Some(last) = &style.last {
+            contents.push((last.replace(HOLDER, holder), false));
+        }
+
+        let mut result = Self {
+            contents,
+            current_year,
+            patterns: Vec::new(),
+            style,
+            this_year,
+        };
+
+        for index in 0..result.contents.len() {
+            let pattern =
+                regex::escape(&normalise(&result.render(index, None)))
+                    .replace(&regex::escape(YEAR), YEARS);
+
+            result
+                .patterns
+                .push(Regex::new(&format!("^{pattern}$")).map_err(|e| {
+                    eprintln!("{e}");
+                    ExitCode::Config
+                })?);
+        }
+
+        Ok(result)
+    }
+
+    /// Compare the given lines to this header.
+    ///
+    /// The deviation, if any, is returned as line, column, and message, with
+    /// the line counting from 1 relative to the first given line.
+    pub fn check(&self, lines: &[&str]) -> Option<(usize, usize, String)> {
+        match self.deviation(lines)? {
+            Deviation::Differs(index) => Some((
+                index + 1,
+                1,
+                format!(
+                    "License header deviates from the template in line {}.",
+                    index + 1
+                ),
+            )),
+            Deviation::Missing => {
+                Some((1, 1, "Missing license header.".to_string()))
+            }
+            Deviation::Outdated(index, first, last) => {
+                let line = lines[index];
+                let column = line
+                    .find(&first.to_string())
+                    .map_or(1, |i| line[..i].chars().count() + 1);
+
+                Some((
+                    index + 1,
+                    column,
+                    format!(
+                        "Copyright year {last} outdated, expected {}.",
+                        self.this_year
+                    ),
+                ))
+            }
+        }
+    }
+
+    /// Insert this header if it is missing or update its year range if it is
+    /// outdated.
+    pub fn fix(&self, data: &str) -> String {
+        let (bom, shebang, body) = split(data);
+        let lines = body.lines().collect::<Vec<_>>();
+        let ending =
+            if data.split('\n').next().is_some_and(|l| l.ends_with('\r')) {
+                "\r\n"
+            } else {
+                "\n"
+            };
+        let mut result = format!("{bom}{shebang}");
+
+        match self.deviation(&lines) {
+            Some(Deviation::Missing) => {
+                if !shebang.is_empty() && !shebang.ends_with('\n') {
+                    result.push_str(ending);
+                }
+
+                for index in 0..self.contents.len() {
+                    result.push_str(
+                        &self.render(index, Some(&self.this_year.to_string())),
+                    );
+                    result.push_str(ending);
+                }
+
+                if !body.is_empty() && !body.starts_with(['\r', '\n']) {
+                    result.push_str(ending);
+                }
+
+                result.push_str(body);
+            }
+            Some(Deviation::Outdated(index, first, _)) => {
+                let years = format!("{first}-{}", self.this_year);
+
+                for (i, piece) in body.split_inclusive('\n').enumerate() {
+                    if i == index {
+                        let text = piece.trim_end_matches(['\r', '\n']);
+
+                        result.push_str(&self.render(index, Some(&years)));
+                        result.push_str(&piece[text.len()..]);
+                    } else {
+                        result.push_str(piece);
+                    }
+                }
+            }
+            Some(Deviation::Differs(_)) | None => result.push_str(body),
+        }
+
+        result
+    }
+
+    /// The count of lines of this header.
+    pub const fn len(&self) -> usize {
+        self.contents.len()
+    }
+
+    fn deviation(&self, lines: &[&str]) -> Option<Deviation> {
+        let mut outdated = None;
+
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            let line = normalise(lines.get(index).copied().unwrap_or_default());
+            let Some(captures) = pattern.captures(&line) else {
+                return Some(if index == 0 {
+                    Deviation::Missing
+                } else {
+                    Deviation::Differs(index)
+                });
+            };
+
+            if outdated.is_none() {
+                outdated = self.outdated(&captures).map(|(first, last)| {
+                    Deviation::Outdated(index, first, last)
+                });
+            }
+        }
+
+        outdated
+    }
+
+    fn outdated(&self, captures: &regex::Captures) -> Option<(i32, i32)> {
+        let first = captures.get(1)?.as_str().parse::<i32>().ok()?;
+        let last = captures
+            .get(2)
+            .and_then(|m| m.as_str().parse::<i32>().ok())
+            .unwrap_or(first);
+
+        (self.current_year && last < self.this_year).then_some((first, last))
+    }
+
+    fn render(&self, index: usize, year: Option<&str>) -> String {
+        let (content, styled) = &self.contents[index];
+        let content = year.map_or_else(
+            || content.clone(),
+            |year| content.replace(YEAR, year),
+        );
+
+        if *styled {
+            self.style.render(&content)
+        } else {
+            content
+        }
+    }
+}
+
+/// The ways a file can deviate from a license header.
+enum Deviation {
+    /// The line at the given index does not match the template.
+    Differs(usize),
+
+    /// The header is missing entirely.
+    Missing,
+
+    /// The line at the given index states an outdated year range.
+    Outdated(usize, i32, i32),
+}
+
+/// The comment styles to use by default, by extension.
+const DEFAULT_STYLES: &[(&[&str], &str)] = &[
+    (
+        &[
+            "c", "cc", "cpp", "cs", "cxx", "dart", "go", "h", "hpp", "java",
+            "js", "jsx", "kt", "rs", "scala", "swift", "ts", "tsx",
+        ],
+        "// ",
+    ),
+    (
+        &[
+            "bash", "cmake", "jl", "mk", "nim", "pl", "ps1", "py", "r", "rb",
+            "sh", "toml", "yaml", "yml", "zsh",
+        ],
+        "# ",
+    ),
+    (&["ada", "elm", "hs", "lua", "sql"], "-- "),
+    (&["bib", "sty", "tex"], "% "),
+];
+
+/// Split the given data into the BOM, the shebang line, and the remainder.
+fn split(data: &str) -> (&str, &str, &str) {
+    let (bom, data) = data
+        .strip_prefix('\u{feff}')
+        .map_or(("", data), |data| ("\u{feff}", data));
+    let shebang = if data.starts_with("#!") {
+        data.split_inclusive('\n').next().unwrap_or_default()
+    } else {
+        ""
+    };
+
+    (bom, shebang, &data[shebang.len()..])
+}
+
+/// Collapse all white space to single spaces.
+fn normalise(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/******************************************************************************/
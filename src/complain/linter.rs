@@ -0,0 +1,1351 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{
+    baseline::Baseline,
+    changes::Changes,
+    config::{globs, normalise, Config, Overrides},
+    editorconfig::EditorConfig,
+    header::Template,
+    mercy::Mercy,
+    profile::Profile,
+    rules::{self, Rule},
+    Diagnostic, OutputFormat, Severity,
+};
+use crate::ceprintlns;
+use aeruginous_io::OptionTruncation;
+use anstyle::AnsiColor;
+use globset::GlobSet;
+use rayon::prelude::*;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+use sysexits::Result;
+
+/// Complain about certain stylistic issues.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(clap::Parser, Clone)]
+pub struct Complain {
+    /// The file of known violations to tolerate.
+    #[arg(long, short)]
+    baseline: Option<PathBuf>,
+
+    /// The configuration file with settings for certain paths.
+    #[arg(long, short)]
+    config: Option<PathBuf>,
+
+    /// Preview the automatic fixes as unified diff.
+    ///
+    /// Reports in any other format than `human` then need to be written to a
+    /// file by `--output`.
+    #[arg(long)]
+    diff: bool,
+
+    /// Only report violations in the lines changed since the given revision.
+    #[arg(long)]
+    diff_base: Option<String>,
+
+    /// The globs of paths to skip.
+    #[arg(long, short = 'x')]
+    exclude: Vec<String>,
+
+    /// The files to analyse.
+    ///
+    /// Directories are searched recursively, skipping the paths ignored by
+    /// `.gitignore`, `.ignore`, and `.complainignore` files.  `-` reads from
+    /// [`std::io::Stdin`].
+    files: Vec<PathBuf>,
+
+    /// Fix the violations in place, as far as possible.
+    #[arg(long)]
+    fix: bool,
+
+    /// The format to report the violations in.
+    #[arg(default_value = "human", long, short)]
+    format: OutputFormat,
+
+    /// Whether to ignore UTF-8 BOMs at the beginning of files.
+    #[arg(long)]
+    ignore_byte_order_mark: bool,
+
+    /// Whether to ignore CRLFs.
+    #[arg(long)]
+    ignore_carriage_return_line_feeds: bool,
+
+    /// Whether to ignore too many consecutive blank lines.
+    #[arg(long)]
+    ignore_consecutive_blank_lines: bool,
+
+    /// Whether to ignore the settings of `.editorconfig` files.
+    #[arg(long)]
+    ignore_editorconfig: bool,
+
+    /// Whether to ignore invisible and bidirectional control characters.
+    #[arg(long)]
+    ignore_invisible_characters: bool,
+
+    /// Whether to ignore missing or outdated license headers.
+    #[arg(long)]
+    ignore_license_header: bool,
+
+    /// Whether to ignore line width issues.
+    #[arg(long)]
+    ignore_line_width_issues: bool,
+
+    /// Whether to ignore Markdown headings without surrounding blank lines.
+    #[arg(long)]
+    ignore_markdown_heading_spacing: bool,
+
+    /// Whether to ignore leftover merge conflict markers.
+    #[arg(long)]
+    ignore_merge_conflict_markers: bool,
+
+    /// Whether to ignore if a file should not be terminated by a line feed.
+    #[arg(long)]
+    ignore_missing_final_line_feed: bool,
+
+    /// Whether to ignore the usage of mixed indentation units.
+    #[arg(long)]
+    ignore_mixed_indentation: bool,
+
+    /// Whether to ignore the usage of both CRLFs and LFs within one file.
+    #[arg(long)]
+    ignore_mixed_line_endings: bool,
+
+    /// Whether to ignore the built-in profiles for certain languages.
+    #[arg(long)]
+    ignore_profiles: bool,
+
+    /// Whether to ignore tabs within lines.
+    #[arg(long)]
+    ignore_tabs_within_lines: bool,
+
+    /// Whether to ignore blank lines at the end of files.
+    #[arg(long)]
+    ignore_trailing_blank_lines: bool,
+
+    /// Whether to ignore TWS.
+    #[arg(long)]
+    ignore_trailing_white_space_characters: bool,
+
+    /// Whether to ignore the usage of wrong indentation units.
+    #[arg(long)]
+    ignore_wrong_indentation: bool,
+
+    /// Whether to ignore YAML files indented by tabs.
+    #[arg(long)]
+    ignore_yaml_tab_indentation: bool,
+
+    /// The indentation unit, defaulting to `spaces`.
+    #[arg(long, short)]
+    indent_by: Option<IndentationUnit>,
+
+    /// The maximum line width to check for, defaulting to 80.
+    #[arg(long, short, visible_aliases = ["length", "line", "width"])]
+    line_width: Option<usize>,
+
+    /// The maximum count of consecutive blank lines.
+    #[arg(default_value = "2", long, visible_aliases = ["blank-lines"])]
+    max_blank_lines: usize,
+
+    /// The file to write the report to, defaulting to [`std::io::Stdout`], if
+    /// omitted; ignored by the human-readable format.
+    #[arg(long = "output", short)]
+    output_file: Option<PathBuf>,
+
+    /// Remove the fixed violations from the baseline.
+    #[arg(long, requires = "baseline")]
+    prune_baseline: bool,
+
+    /// Also report mercy markers which do not suppress any violation.
+    #[arg(long)]
+    report_unused_mercy: bool,
+
+    /// The path to report and to select the settings for when reading from
+    /// [`std::io::Stdin`].
+    #[arg(long)]
+    stdin_filename: Option<PathBuf>,
+
+    /// The tab stop width to expand tabs and to re-indent lines with,
+    /// defaulting to 4.
+    #[arg(
+        long,
+        short,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new()
+            .range(1..),
+        visible_aliases = ["tab-stop"]
+    )]
+    tab_width: Option<usize>,
+
+    /// Also print results for files which do not violate against any lint.
+    #[arg(long)]
+    verbose: bool,
+
+    /// Record all current violations as baseline in the given file.
+    #[arg(long)]
+    write_baseline: Option<PathBuf>,
+}
+
+impl Complain {
+    /// Check the configured files without reporting the violations.
+    ///
+    /// The violations will be returned ordered by the files they were found in.
+    ///
+    /// # Errors
+    ///
+    /// See [`aeruginous_io::PathBufLikeReader::read_loudly`].
+    pub fn diagnose(&self) -> Result<Vec<Diagnostic>> {
+        self.wrap().diagnose(None)
+    }
+
+    /// Check a single file without reporting the violations.
+    ///
+    /// # Errors
+    ///
+    /// See [`aeruginous_io::PathBufLikeReader::read_loudly`].
+    pub fn diagnose_file<T>(&self, path: T) -> Result<Vec<Diagnostic>>
+    where
+        PathBuf: From<T>,
+    {
+        self.wrap().diagnose(Some(PathBuf::from(path)))
+    }
+
+    /// Check the given contents as if they were stored at the given path
+    /// without reporting the violations.
+    ///
+    /// The path is only used to select the settings for the contents and to
+    /// report the violations with; it does not need to exist.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::diagnose`].
+    pub fn diagnose_str<T>(
+        &self,
+        path: T,
+        data: &str,
+    ) -> Result<Vec<Diagnostic>>
+    where
+        PathBuf: From<T>,
+    {
+        let mut logic = self.wrap();
+        let job = logic
+            .configure(&PathBuf::from(path), Some(data.as_bytes().into()))?;
+
+        logic.base.diff = false;
+        logic.base.fix = false;
+        logic.evaluate(&[job], false)
+    }
+
+    /// Set the file of known violations to tolerate.
+    pub fn baseline<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.baseline = Some(PathBuf::from(path));
+    }
+
+    /// Set the configuration file with settings for certain paths.
+    pub fn config<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.config = Some(PathBuf::from(path));
+    }
+
+    /// Preview the automatic fixes as unified diff on [`std::io::Stdout`].
+    pub const fn diff(&mut self) {
+        self.diff = true;
+    }
+
+    /// Skip the paths matching the given glob.
+    pub fn exclude<T>(&mut self, pattern: T)
+    where
+        String: From<T>,
+    {
+        self.exclude.push(String::from(pattern));
+    }
+
+    /// Only report violations in the lines changed since the given revision.
+    pub fn diff_base<T>(&mut self, revision: T)
+    where
+        String: From<T>,
+    {
+        self.diff_base = Some(String::from(revision));
+    }
+
+    /// Fix the violations in place, as far as possible.
+    pub const fn fix(&mut self) {
+        self.fix = true;
+    }
+
+    /// Set another output format.
+    pub const fn format(&mut self, f: OutputFormat) {
+        self.format = f;
+    }
+
+    /// Ignore UTF-8 BOMs at the beginning of files.
+    pub const fn ignore_byte_order_mark(&mut self) {
+        self.ignore_byte_order_mark = true;
+    }
+
+    /// Ignore CRLFs.
+    pub fn ignore_carriage_return_line_feeds(&mut self) {
+        self.ignore_carriage_return_line_feeds = true;
+    }
+
+    /// Ignore too many consecutive blank lines.
+    pub const fn ignore_consecutive_blank_lines(&mut self) {
+        self.ignore_consecutive_blank_lines = true;
+    }
+
+    /// Ignore the settings of `.editorconfig` files.
+    pub const fn ignore_editorconfig(&mut self) {
+        self.ignore_editorconfig = true;
+    }
+
+    /// Ignore invisible and bidirectional control characters.
+    pub const fn ignore_invisible_characters(&mut self) {
+        self.ignore_invisible_characters = true;
+    }
+
+    /// Ignore missing or outdated license headers.
+    pub const fn ignore_license_header(&mut self) {
+        self.ignore_license_header = true;
+    }
+
+    /// Ignore too long lines.
+    pub fn ignore_line_width_issues(&mut self) {
+        self.ignore_line_width_issues = true;
+    }
+
+    /// Ignore Markdown headings without surrounding blank lines.
+    pub const fn ignore_markdown_heading_spacing(&mut self) {
+        self.ignore_markdown_heading_spacing = true;
+    }
+
+    /// Ignore leftover merge conflict markers.
+    pub const fn ignore_merge_conflict_markers(&mut self) {
+        self.ignore_merge_conflict_markers = true;
+    }
+
+    /// Ignore missing trailing newline characters.
+    pub fn ignore_missing_final_line_feed(&mut self) {
+        self.ignore_missing_final_line_feed = true;
+    }
+
+    /// Ignore the application of multiple indentation units.
+    pub fn ignore_mixed_indentation(&mut self) {
+        self.ignore_mixed_indentation = true;
+    }
+
+    /// Ignore the usage of both CRLFs and LFs within one file.
+    pub const fn ignore_mixed_line_endings(&mut self) {
+        self.ignore_mixed_line_endings = true;
+    }
+
+    /// Ignore the built-in profiles for certain languages.
+    pub const fn ignore_profiles(&mut self) {
+        self.ignore_profiles = true;
+    }
+
+    /// Ignore tab characters in input lines.
+    pub fn ignore_tabs_within_lines(&mut self) {
+        self.ignore_tabs_within_lines = true;
+    }
+
+    /// Ignore blank lines at the end of files.
+    pub const fn ignore_trailing_blank_lines(&mut self) {
+        self.ignore_trailing_blank_lines = true;
+    }
+
+    /// Ignore lines ending with spaces and / or tab characters.
+    pub fn ignore_trailing_white_space_characters(&mut self) {
+        self.ignore_trailing_white_space_characters = true;
+    }
+
+    /// Ignore applications of the opposite indentation unit.
+    pub fn ignore_wrong_indentation(&mut self) {
+        self.ignore_wrong_indentation = true;
+    }
+
+    /// Ignore YAML files indented by tabs.
+    pub const fn ignore_yaml_tab_indentation(&mut self) {
+        self.ignore_yaml_tab_indentation = true;
+    }
+
+    /// Set another indentation unit.
+    pub fn indent_by(&mut self, i: IndentationUnit) {
+        self.indent_by = Some(i);
+    }
+
+    /// Set another maximum count of consecutive blank lines.
+    pub const fn max_blank_lines(&mut self, count: usize) {
+        self.max_blank_lines = count;
+    }
+
+    /// Process the input data.
+    ///
+    /// # Errors
+    ///
+    /// See
+    ///
+    /// - [`aeruginous_io::OptionTruncation::truncate_loudly`]
+    /// - [`aeruginous_io::PathBufLikeReader::read_loudly`]
+    /// - [`crate::ColourMessage`]
+    /// - [`sysexits::ExitCode::DataErr`]
+    pub fn main(&self) -> Result<()> {
+        self.wrap().main()
+    }
+
+    /// Create a new instance.
+    #[must_use]
+    pub const fn new(files: Vec<PathBuf>) -> Self {
+        Self {
+            baseline: None,
+            config: None,
+            diff: false,
+            diff_base: None,
+            exclude: Vec::new(),
+            files,
+            fix: false,
+            format: OutputFormat::Human,
+            ignore_byte_order_mark: false,
+            ignore_carriage_return_line_feeds: false,
+            ignore_consecutive_blank_lines: false,
+            ignore_editorconfig: false,
+            ignore_invisible_characters: false,
+            ignore_license_header: false,
+            ignore_line_width_issues: false,
+            ignore_markdown_heading_spacing: false,
+            ignore_merge_conflict_markers: false,
+            ignore_missing_final_line_feed: false,
+            ignore_mixed_indentation: false,
+            ignore_mixed_line_endings: false,
+            ignore_profiles: false,
+            ignore_tabs_within_lines: false,
+            ignore_trailing_blank_lines: false,
+            ignore_trailing_white_space_characters: false,
+            ignore_wrong_indentation: false,
+            ignore_yaml_tab_indentation: false,
+            indent_by: None,
+            line_width: None,
+            max_blank_lines: 2,
+            output_file: None,
+            prune_baseline: false,
+            report_unused_mercy: false,
+            stdin_filename: None,
+            tab_width: None,
+            verbose: false,
+            write_baseline: None,
+        }
+    }
+
+    /// Set the file to write the report to.
+    pub fn output_file<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.output_file = Some(PathBuf::from(path));
+    }
+
+    /// Push a new path to the list of paths to process.
+    pub fn push<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.files.push(PathBuf::from(path));
+    }
+
+    /// Process this instance.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::main`].
+    pub fn process(&self) -> Result<usize> {
+        self.wrap().process()
+    }
+
+    /// Remove the fixed violations from the baseline.
+    pub const fn prune_baseline(&mut self) {
+        self.prune_baseline = true;
+    }
+
+    /// Also report mercy markers which do not suppress any violation.
+    pub const fn report_unused_mercy(&mut self) {
+        self.report_unused_mercy = true;
+    }
+
+    /// Set the path to report and to select the settings for when reading from
+    /// [`std::io::Stdin`].
+    pub fn stdin_filename<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.stdin_filename = Some(PathBuf::from(path));
+    }
+
+    /// Set another tab stop width, being at least 1.
+    pub const fn tab_width(&mut self, width: usize) {
+        self.tab_width = Some(if width == 0 { 1 } else { width });
+    }
+
+    /// Record all current violations as baseline in the given file.
+    pub fn write_baseline<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.write_baseline = Some(PathBuf::from(path));
+    }
+
+    /// Query the current state of settings.
+    #[must_use]
+    pub const fn state(
+        &self,
+    ) -> (&Vec<PathBuf>, [bool; 7], IndentationUnit, usize) {
+        (
+            &self.files,
+            [
+                self.ignore_carriage_return_line_feeds,
+                self.ignore_line_width_issues,
+                self.ignore_missing_final_line_feed,
+                self.ignore_mixed_indentation,
+                self.ignore_tabs_within_lines,
+                self.ignore_trailing_white_space_characters,
+                self.ignore_wrong_indentation,
+            ],
+            match self.indent_by {
+                Some(unit) => unit,
+                None => IndentationUnit::Spaces,
+            },
+            match self.line_width {
+                Some(width) => width,
+                None => LINE_WIDTH,
+            },
+        )
+    }
+
+    const fn apply(&mut self, overrides: &Overrides) {
+        macro_rules! apply {
+            ( $( $field:ident ),+ ) => {
+                $(
+                    if let Some(value) = overrides.$field {
+                        self.$field = value;
+                    }
+                )+
+            };
+        }
+
+        apply!(
+            ignore_byte_order_mark,
+            ignore_carriage_return_line_feeds,
+            ignore_consecutive_blank_lines,
+            ignore_invisible_characters,
+            ignore_license_header,
+            ignore_line_width_issues,
+            ignore_markdown_heading_spacing,
+            ignore_merge_conflict_markers,
+            ignore_missing_final_line_feed,
+            ignore_mixed_indentation,
+            ignore_mixed_line_endings,
+            ignore_tabs_within_lines,
+            ignore_trailing_blank_lines,
+            ignore_trailing_white_space_characters,
+            ignore_wrong_indentation,
+            ignore_yaml_tab_indentation,
+            max_blank_lines
+        );
+
+        if let Some(unit) = overrides.indent_by {
+            self.indent_by = Some(unit);
+        }
+
+        if let Some(width) = overrides.line_width {
+            self.line_width = Some(width);
+        }
+
+        if let Some(width) = overrides.tab_width {
+            self.tab_width(width);
+        }
+    }
+
+    fn wrap(&self) -> Logic {
+        let mut base = self.clone();
+        let files = std::mem::take(&mut base.files);
+
+        Logic {
+            base,
+            baseline: None,
+            changes: None,
+            config: None,
+            editorconfig: EditorConfig::default(),
+            files,
+            known: Baseline::default(),
+            linted: BTreeSet::new(),
+            recorded: Baseline::default(),
+        }
+    }
+}
+
+impl Default for Complain {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+/// The possible indentation units.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndentationUnit {
+    /// Indent by spaces.
+    #[default]
+    Spaces,
+
+    /// Indent by tabs.
+    Tabs,
+}
+
+crate::enum_trait!(IndentationUnit {
+    Spaces <-> "spaces",
+    Tabs <-> "tabs"
+});
+
+const RULES: [&str; 16] = [
+    "ÆC-0001", "ÆC-0002", "ÆC-0003", "ÆC-0004", "ÆC-0005", "ÆC-0006",
+    "ÆC-0007", "ÆC-0008", "ÆC-0009", "ÆC-0010", "ÆC-0011", "ÆC-0012",
+    "ÆC-0013", "ÆC-0014", "ÆC-0015", "ÆC-0016",
+];
+
+const FILE_RULES: [&str; 5] =
+    ["ÆC-0001", "ÆC-0008", "ÆC-0009", "ÆC-0010", "ÆC-0014"];
+
+/// The number of files to keep in memory at once.
+const BATCH_SIZE: usize = 256;
+
+/// The default maximum line width.
+const LINE_WIDTH: usize = 80;
+
+/// The default tab stop width.
+const TAB_WIDTH: usize = 4;
+
+/// The path to read [`std::io::Stdin`] for.
+const STDIN: &str = "-";
+
+/// The checks of a single file with its own settings.
+struct Job {
+    cli: Complain,
+    contents: Option<Vec<u8>>,
+    custom: Vec<rules::Custom>,
+    file: PathBuf,
+    header: Option<Template>,
+    indent_by: IndentationUnit,
+    line_width: usize,
+    profile: Option<Profile>,
+    tab_width: usize,
+}
+
+impl Job {
+    fn advance(&self, column: usize, c: char) -> usize {
+        rules::advance(column, c, self.tab_width)
+    }
+
+    fn check(&self, data: &str, changes: Option<&Changes>) -> Vec<Diagnostic> {
+        let mercy = Mercy::new(data);
+        let mut active = self.rules();
+        let findings = rules::check(data, &mut active);
+        let mut diagnostics = Vec::new();
+
+        for (rule, findings) in active.iter().zip(findings) {
+            for finding in findings {
+                if !mercy.spares(rule.code(), finding.line) {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            self.file.clone(),
+                            finding.line,
+                            finding.column,
+                            rule.code().to_string(),
+                            rule.severity(),
+                            finding.message,
+                            rule.colour(),
+                        )
+                        .with_length(finding.length),
+                    );
+                }
+            }
+        }
+
+        if self.cli.report_unused_mercy {
+            let rules = RULES
+                .iter()
+                .copied()
+                .filter(|rule| self.enabled(rule))
+                .chain(self.custom.iter().map(|rule| rule.id.as_str()))
+                .collect::<Vec<_>>();
+
+            for (position, rule) in mercy.unused(&rules) {
+                diagnostics.push(Diagnostic::new(
+                    self.file.clone(),
+                    position.0,
+                    position.1,
+                    "ÆC-0000".to_string(),
+                    Severity::Warning,
+                    format!("Mercy for {rule} without any effect."),
+                    AnsiColor::Green,
+                ));
+            }
+        }
+
+        if let Some(changes) = changes {
+            diagnostics.retain(|d| {
+                if FILE_RULES.contains(&d.rule().as_str()) {
+                    changes.contains(&self.file)
+                } else {
+                    changes.contains_line(&self.file, d.line())
+                }
+            });
+        }
+
+        diagnostics
+    }
+
+    fn enabled(&self, rule: &str) -> bool {
+        match rule {
+            "ÆC-0001" => !self.cli.ignore_missing_final_line_feed,
+            "ÆC-0002" => !self.cli.ignore_carriage_return_line_feeds,
+            "ÆC-0003" => !self.cli.ignore_line_width_issues,
+            "ÆC-0004" => !self.cli.ignore_trailing_white_space_characters,
+            "ÆC-0005" => !self.cli.ignore_wrong_indentation,
+            "ÆC-0006" => !self.cli.ignore_mixed_indentation,
+            "ÆC-0007" => !self.cli.ignore_tabs_within_lines,
+            "ÆC-0008" => !self.cli.ignore_byte_order_mark,
+            "ÆC-0009" => !self.cli.ignore_trailing_blank_lines,
+            "ÆC-0010" => !self.cli.ignore_mixed_line_endings,
+            "ÆC-0011" => !self.cli.ignore_consecutive_blank_lines,
+            "ÆC-0012" => !self.cli.ignore_merge_conflict_markers,
+            "ÆC-0013" => !self.cli.ignore_invisible_characters,
+            "ÆC-0014" => {
+                !self.cli.ignore_license_header && self.header.is_some()
+            }
+            "ÆC-0015" => {
+                !self.cli.ignore_markdown_heading_spacing
+                    && self.profile == Some(Profile::Markdown)
+            }
+            "ÆC-0016" => {
+                !self.cli.ignore_yaml_tab_indentation
+                    && self.profile == Some(Profile::Yaml)
+            }
+            _ => false,
+        }
+    }
+
+    fn expand_tabs(&self, line: &str) -> String {
+        let indentation = line.len() - line.trim_start().len();
+        let content = line.trim_end().len().max(indentation);
+        let mut column = self.width(&line[..indentation]);
+        let mut result = line[..indentation].to_string();
+
+        for c in line[indentation..content].chars() {
+            if c == '\t' {
+                let spaces = self.tab_width - column % self.tab_width;
+
+                result.push_str(&" ".repeat(spaces));
+                column += spaces;
+            } else {
+                result.push(c);
+                column = self.advance(column, c);
+            }
+        }
+
+        result.push_str(&line[content..]);
+        result
+    }
+
+    fn fix(&self, data: &str) -> String {
+        let mercy = Mercy::new(data);
+        let mut result = String::new();
+        let fixes =
+            |rule, line| self.enabled(rule) && !mercy.spares(rule, line);
+        let markdown = self.profile == Some(Profile::Markdown);
+        let mut last = 0;
+
+        for line in rules::lines(data) {
+            last = line.number;
+
+            if mercy.marks(line.number) {
+                result.push_str(line.text);
+                result.push_str(line.ending);
+                continue;
+            }
+
+            let n = line.number;
+            let mut text = line.text.to_string();
+
+            let hard_break = markdown && rules::is_hard_break(&text);
+
+            if fixes("ÆC-0004", n) && !hard_break {
+                text.truncate(text.trim_end().len());
+            }
+
+            text = self.reindent(
+                &text,
+                fixes("ÆC-0005", n) || fixes("ÆC-0016", n),
+                fixes("ÆC-0006", n),
+            );
+
+            if fixes("ÆC-0007", n) {
+                text = self.expand_tabs(&text);
+            }
+
+            result.push_str(&text);
+            result.push_str(if line.ending == "\r\n" && fixes("ÆC-0002", n) {
+                "\n"
+            } else {
+                line.ending
+            });
+        }
+
+        if !result.ends_with('\n') && fixes("ÆC-0001", last.max(1)) {
+            result.push('\n');
+        }
+
+        if let Some(template) = &self.header {
+            if fixes("ÆC-0014", 1) {
+                result = template.fix(&result);
+            }
+        }
+
+        result
+    }
+
+    fn reindent(&self, line: &str, wrong: bool, mixed: bool) -> String {
+        let (indentation, content) =
+            line.split_at(line.len() - line.trim_start().len());
+        let wrong = wrong
+            && match self.indent_by {
+                IndentationUnit::Spaces => indentation.contains('\t'),
+                IndentationUnit::Tabs => indentation.starts_with(' '),
+            };
+        let mixed =
+            mixed && indentation.contains('\t') && indentation.contains(' ');
+
+        if wrong || mixed {
+            let width = self.width(indentation);
+
+            match self.indent_by {
+                IndentationUnit::Spaces => " ".repeat(width) + content,
+                IndentationUnit::Tabs
+                    if width.is_multiple_of(self.tab_width) =>
+                {
+                    "\t".repeat(width / self.tab_width) + content
+                }
+                IndentationUnit::Tabs => line.to_string(),
+            }
+        } else {
+            line.to_string()
+        }
+    }
+
+    fn rules(&self) -> Vec<Box<dyn Rule>> {
+        let markdown = self.profile == Some(Profile::Markdown);
+        let all: [Box<dyn Rule>; 15] = [
+            Box::new(rules::FinalLineFeed),
+            Box::new(rules::CarriageReturnLineFeed),
+            Box::new(rules::LineWidth {
+                link_definitions: markdown,
+                maximum: self.line_width,
+                tab_width: self.tab_width,
+            }),
+            Box::new(rules::TrailingWhiteSpace {
+                hard_breaks: markdown,
+            }),
+            Box::new(rules::WrongIndentation {
+                indent_by: self.indent_by,
+            }),
+            Box::new(rules::MixedIndentation),
+            Box::new(rules::TabsWithinLines),
+            Box::new(rules::ByteOrderMark),
+            Box::<rules::TrailingBlankLines>::default(),
+            Box::<rules::MixedLineEndings>::default(),
+            Box::new(rules::ConsecutiveBlankLines::new(
+                self.cli.max_blank_lines,
+            )),
+            Box::<rules::MergeConflictMarkers>::default(),
+            Box::new(rules::InvisibleCharacters),
+            Box::<rules::HeadingSpacing>::default(),
+            Box::new(rules::YamlTabIndentation),
+        ];
+
+        all.into_iter()
+            .chain(self.header.clone().map(|template| {
+                Box::new(rules::LicenseHeader::new(template)) as Box<dyn Rule>
+            }))
+            .filter(|rule| self.enabled(rule.code()))
+            .chain(
+                self.custom
+                    .iter()
+                    .map(|rule| Box::new(rule.clone()) as Box<dyn Rule>),
+            )
+            .collect()
+    }
+
+    fn run(&self, changes: Option<&Changes>) -> Outcome {
+        let bytes = match &self.contents {
+            Some(contents) => contents.clone(),
+            None => match std::fs::read(&self.file) {
+                Ok(bytes) => bytes,
+                Err(error) => return Outcome::Unreadable(error),
+            },
+        };
+
+        if bytes.iter().take(8192).any(|&b| b == 0) {
+            return Outcome::Binary;
+        }
+
+        let Ok(data) = String::from_utf8(bytes) else {
+            return Outcome::NotUtf8;
+        };
+        let fixed = (self.cli.fix || self.cli.diff)
+            .then(|| self.fix(&data))
+            .filter(|fixed| *fixed != data);
+        let diagnostics = self.check(
+            if self.cli.fix {
+                fixed.as_deref().unwrap_or(&data)
+            } else {
+                &data
+            },
+            changes,
+        );
+
+        Outcome::Checked {
+            data,
+            diagnostics,
+            fixed,
+        }
+    }
+
+    fn width(&self, text: &str) -> usize {
+        text.chars().fold(0, |column, c| self.advance(column, c))
+    }
+}
+
+/// The result of checking a single file.
+enum Outcome {
+    Binary,
+    Checked {
+        data: String,
+        diagnostics: Vec<Diagnostic>,
+        fixed: Option<String>,
+    },
+    NotUtf8,
+    Unreadable(std::io::Error),
+}
+
+struct Logic {
+    base: Complain,
+    baseline: Option<Baseline>,
+    changes: Option<Changes>,
+    config: Option<Config>,
+    editorconfig: EditorConfig,
+    files: Vec<PathBuf>,
+    known: Baseline,
+    linted: BTreeSet<String>,
+    recorded: Baseline,
+}
+
+impl Logic {
+    fn configure(
+        &mut self,
+        f: &Path,
+        contents: Option<Vec<u8>>,
+    ) -> Result<Job> {
+        if self.baseline.is_none() {
+            self.baseline = Some(self.base.baseline.as_ref().map_or_else(
+                || Ok(Baseline::default()),
+                |b| Baseline::load(b),
+            )?);
+        }
+
+        if self.changes.is_none() {
+            if let Some(revision) = &self.base.diff_base {
+                self.changes = Some(Changes::new(revision)?);
+            }
+        }
+
+        if self.config.is_none() {
+            self.config =
+                Some(self.base.config.as_ref().map_or_else(
+                    || Ok(Config::default()),
+                    |c| Config::load(c),
+                )?);
+        }
+
+        let mut cli = self.base.clone();
+        let mut custom = Vec::new();
+        let mut header = None;
+        let profile = if self.base.ignore_profiles {
+            None
+        } else {
+            Profile::detect(f)
+        };
+
+        if let Some(profile) = profile {
+            cli.apply(&profile.overrides());
+        }
+
+        if !self.base.ignore_editorconfig {
+            cli.apply(&self.editorconfig.overrides(f)?);
+        }
+
+        if let Some(config) = &self.config {
+            for overrides in config.overrides(f) {
+                cli.apply(overrides);
+            }
+
+            custom.extend(config.rules(f).cloned());
+            header = config.header(f).cloned();
+        }
+
+        Ok(Job {
+            indent_by: self
+                .base
+                .indent_by
+                .or(cli.indent_by)
+                .unwrap_or(IndentationUnit::Spaces),
+            line_width: self
+                .base
+                .line_width
+                .or(cli.line_width)
+                .unwrap_or(LINE_WIDTH),
+            tab_width: self
+                .base
+                .tab_width
+                .or(cli.tab_width)
+                .unwrap_or(TAB_WIDTH),
+            cli,
+            contents,
+            custom,
+            file: f.to_path_buf(),
+            header,
+            profile,
+        })
+    }
+
+    fn diagnose(&mut self, file: Option<PathBuf>) -> Result<Vec<Diagnostic>> {
+        let files = file.map_or_else(|| self.files(), |f| Ok(vec![f]))?;
+
+        self.base.diff = false;
+        self.base.fix = false;
+        self.inspect(&files, false)
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        let excluded = globs(&self.base.exclude)?;
+        let mut result = Vec::new();
+
+        for f in self
+            .files
+            .iter()
+            .filter(|f| !Self::excluded(&excluded, self.name(f)))
+        {
+            if f.is_dir() {
+                Self::files_in(f, &excluded, &mut result)?;
+            } else {
+                result.push(f.clone());
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn files_in(
+        directory: &Path,
+        excluded: &GlobSet,
+        result: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let excluded = excluded.clone();
+
+        for entry in ignore::WalkBuilder::new(directory)
+            .add_custom_ignore_filename(".complainignore")
+            .filter_entry(move |e| {
+                e.file_name() != ".git" && !Self::excluded(&excluded, e.path())
+            })
+            .follow_links(true)
+            .hidden(false)
+            .require_git(false)
+            .sort_by_file_name(std::ffi::OsStr::cmp)
+            .build()
+        {
+            match entry {
+                Ok(entry) => {
+                    if !entry.path().is_dir() {
+                        result.push(entry.into_path());
+                    }
+                }
+                Err(error) => {
+                    ceprintlns!("Skipped"!Yellow, "{error}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(
+        &mut self,
+        jobs: &[Job],
+        render: bool,
+    ) -> Result<Vec<Diagnostic>> {
+        let changes = self.changes.as_ref();
+        let outcomes = jobs
+            .par_iter()
+            .map(|job| job.run(changes))
+            .collect::<Vec<_>>();
+        let mut reported = Vec::new();
+
+        for (job, outcome) in jobs.iter().zip(outcomes) {
+            let f = &job.file;
+
+            match outcome {
+                Outcome::Binary => {
+                    ceprintlns!(
+                        "Skipped"!Yellow,
+                        "{} (binary file)",
+                        f.display()
+                    );
+                }
+                Outcome::Checked {
+                    data,
+                    mut diagnostics,
+                    fixed,
+                } => {
+                    let data = self.settle(job, data, fixed)?;
+
+                    self.tolerate(f, &data, &mut diagnostics);
+
+                    if render {
+                        self.base.format.render_file(
+                            f,
+                            &data,
+                            &diagnostics,
+                            job.tab_width,
+                            self.base.verbose,
+                            &mut std::io::stderr(),
+                        )?;
+                    }
+
+                    reported.append(&mut diagnostics);
+                }
+                Outcome::NotUtf8 => {
+                    ceprintlns!(
+                        "Skipped"!Yellow,
+                        "{} (not UTF-8)",
+                        f.display()
+                    );
+                }
+                Outcome::Unreadable(error) => {
+                    eprintln!("{}:  {error}", f.display());
+                    return Err(error.into());
+                }
+            }
+        }
+
+        Ok(reported)
+    }
+
+    fn excluded(excluded: &GlobSet, path: &Path) -> bool {
+        normalise(path).ancestors().any(|p| excluded.is_match(p))
+    }
+
+    fn inspect(
+        &mut self,
+        files: &[PathBuf],
+        render: bool,
+    ) -> Result<Vec<Diagnostic>> {
+        let mut reported = Vec::new();
+
+        for batch in files.chunks(BATCH_SIZE) {
+            let mut jobs = Vec::with_capacity(batch.len());
+
+            for f in batch {
+                let contents = if f.as_os_str() == STDIN {
+                    let mut contents = Vec::new();
+
+                    std::io::Read::read_to_end(
+                        &mut std::io::stdin().lock(),
+                        &mut contents,
+                    )
+                    .map_err(|e| {
+                        eprintln!("{STDIN}:  {e}");
+                        e
+                    })?;
+
+                    Some(contents)
+                } else {
+                    None
+                };
+                let name = self.name(f).to_path_buf();
+
+                jobs.push(self.configure(&name, contents)?);
+            }
+
+            reported.append(&mut self.evaluate(&jobs, render)?);
+        }
+
+        Ok(reported)
+    }
+
+    fn main(&mut self) -> Result<()> {
+        if self.process()? == 0 {
+            Ok(())
+        } else {
+            Err(sysexits::ExitCode::DataErr)
+        }
+    }
+
+    fn name<'a>(&'a self, f: &'a Path) -> &'a Path {
+        if f.as_os_str() == STDIN {
+            self.base.stdin_filename.as_deref().unwrap_or(f)
+        } else {
+            f
+        }
+    }
+
+    fn process(&mut self) -> Result<usize> {
+        let files = self.files()?;
+
+        if self.base.format != OutputFormat::Human
+            && self.base.output_file.is_none()
+        {
+            if self.base.diff {
+                ceprintlns!(
+                    "Report"!Red,
+                    "would be mixed with the diffs, please set `--output`."
+                );
+                return Err(sysexits::ExitCode::Usage);
+            }
+
+            if self.base.fix && files.iter().any(|f| f.as_os_str() == STDIN) {
+                ceprintlns!(
+                    "Report"!Red,
+                    "would be mixed with the fixed contents, please set \
+                     `--output`."
+                );
+                return Err(sysexits::ExitCode::Usage);
+            }
+        }
+
+        let reported = self.inspect(&files, true)?;
+
+        if self.base.format != OutputFormat::Human {
+            self.base.format.render(&reported)?.truncate_loudly(
+                self.base.output_file.clone(),
+                std::io::stdout().lock(),
+            )?;
+        }
+
+        if let Some(path) = &self.base.write_baseline {
+            self.recorded.save(path)?;
+        }
+
+        if self.base.prune_baseline {
+            if let Some(path) = &self.base.baseline {
+                if let Some(baseline) = &self.baseline {
+                    self.known.adopt(baseline, &self.linted);
+                }
+
+                self.known.save(path)?;
+            }
+        }
+
+        Ok(reported.len())
+    }
+
+    fn settle(
+        &self,
+        job: &Job,
+        data: String,
+        fixed: Option<String>,
+    ) -> Result<String> {
+        let f = &job.file;
+        let Some(fixed) = fixed else {
+            if self.base.fix && job.contents.is_some() {
+                print!("{data}");
+            }
+
+            return Ok(data);
+        };
+
+        if self.base.diff {
+            print!(
+                "{}",
+                similar::TextDiff::from_lines(&data, &fixed)
+                    .unified_diff()
+                    .header(&f.display().to_string(), &f.display().to_string())
+            );
+        }
+
+        if self.base.fix && job.contents.is_some() {
+            print!("{fixed}");
+            Ok(fixed)
+        } else if self.base.fix {
+            aeruginous_io::PathBufLikeTruncation::truncate_loudly(
+                fixed.clone(),
+                f,
+            )?;
+
+            if self.base.format == OutputFormat::Human {
+                ceprintlns!("Fixed"!Green, "{}", f.display());
+            }
+
+            Ok(fixed)
+        } else {
+            Ok(data)
+        }
+    }
+
+    fn tolerate(
+        &mut self,
+        f: &Path,
+        data: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let file = normalise(f).display().to_string();
+        let lines = data.lines().collect::<Vec<_>>();
+        let baseline = self.baseline.get_or_insert_with(Baseline::default);
+        let record = self.base.write_baseline.is_some();
+
+        self.linted.insert(file.clone());
+
+        diagnostics.retain(|d| {
+            let line = lines.get(d.line() - 1).copied().unwrap_or_default();
+
+            if record {
+                self.recorded.insert(&file, d.rule(), line);
+                false
+            } else if baseline.take(&file, d.rule(), line) {
+                self.known.insert(&file, d.rule(), line);
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/******************************************************************************/
@@ -0,0 +1,147 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use std::cell::Cell;
+
+const PREFIX: &str = "#[aeruginous::mercy::";
+
+/// The suppressions of rules within a file.
+///
+/// A rule is suppressed by a marker naming its number, optionally followed by
+/// the scope of the suppression:
+///
+/// - `#[aeruginous::mercy::0003]` for the line containing the marker,
+/// - `#[aeruginous::mercy::0003::next]` for the following line,
+/// - `#[aeruginous::mercy::0003::start]` for all lines up to and including the
+///   next `#[aeruginous::mercy::0003::end]`, and
+/// - `#[aeruginous::mercy::0003::file]` for the whole file.
+pub struct Mercy {
+    suppressions: Vec<Suppression>,
+}
+
+impl Mercy {
+    /// Check whether the given line contains a marker or lies within a region.
+    pub fn marks(&self, line: usize) -> bool {
+        self.suppressions.iter().any(|s| {
+            s.line == line || (s.region && (s.first..=s.last).contains(&line))
+        })
+    }
+
+    /// Collect the suppressions of the given file content.
+    pub fn new(data: &str) -> Self {
+        let mut open = Vec::new();
+        let mut suppressions = Vec::<Suppression>::new();
+
+        for (line, l) in (1..).zip(data.lines()) {
+            let mut offset = 0;
+
+            while let Some(start) = l[offset..].find(PREFIX).map(|i| offset + i)
+            {
+                let marker = &l[start + PREFIX.len()..];
+                let Some(end) = marker.find(']') else {
+                    break;
+                };
+                let (rule, scope) = marker[..end]
+                    .split_once("::")
+                    .unwrap_or((&marker[..end], ""));
+                let range = match scope {
+                    "" => Some((line, line)),
+                    "file" => Some((1, usize::MAX)),
+                    "next" => Some((line + 1, line + 1)),
+                    "start" => {
+                        open.push(suppressions.len());
+                        Some((line, usize::MAX))
+                    }
+                    "end" => {
+                        if let Some(i) = open
+                            .iter()
+                            .rposition(|&i| suppressions[i].rule == rule)
+                        {
+                            suppressions[open.remove(i)].last = line;
+                        }
+
+                        None
+                    }
+                    _ => None,
+                };
+
+                if let Some((first, last)) = range {
+                    suppressions.push(Suppression {
+                        column: l[..start].chars().count() + 1,
+                        first,
+                        last,
+                        line,
+                        region: scope == "start",
+                        rule: rule.to_string(),
+                        used: Cell::new(false),
+                    });
+                }
+
+                offset = start + PREFIX.len() + end;
+            }
+        }
+
+        Self { suppressions }
+    }
+
+    /// Check whether the given rule is suppressed in the given line.
+    pub fn spares(&self, rule: &str, line: usize) -> bool {
+        let mut result = false;
+
+        for s in &self.suppressions {
+            if s.applies_to(rule) && (s.first..=s.last).contains(&line) {
+                s.used.set(true);
+                result = true;
+            }
+        }
+
+        result
+    }
+
+    /// Collect the suppressions of the given rules which did not suppress any
+    /// violation, yet, as pairs of position and rule.
+    pub fn unused<'a>(
+        &'a self,
+        rules: &'a [&str],
+    ) -> impl Iterator<Item = ((usize, usize), &'a str)> {
+        self.suppressions
+            .iter()
+            .filter(|s| !s.used.get() && rules.iter().any(|r| s.applies_to(r)))
+            .map(|s| ((s.line, s.column), s.rule.as_str()))
+    }
+}
+
+struct Suppression {
+    column: usize,
+    first: usize,
+    last: usize,
+    line: usize,
+    region: bool,
+    rule: String,
+    used: Cell<bool>,
+}
+
+impl Suppression {
+    fn applies_to(&self, rule: &str) -> bool {
+        rule == self.rule
+            || rule.strip_prefix("ÆC-").is_some_and(|r| r == self.rule)
+    }
+}
+
+/******************************************************************************/
@@ -0,0 +1,36 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+mod baseline;
+mod changes;
+mod config;
+mod diagnostic;
+mod editorconfig;
+mod format;
+mod header;
+mod linter;
+mod mercy;
+mod profile;
+mod rules;
+
+pub use diagnostic::{Diagnostic, Severity};
+pub use format::OutputFormat;
+pub use linter::{Complain, IndentationUnit};
+
+/******************************************************************************/
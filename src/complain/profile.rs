@@ -0,0 +1,79 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{config::Overrides, IndentationUnit};
+use std::path::Path;
+
+/// The built-in adjustments of the rules for certain languages.
+///
+/// The profile of a file is selected by its name or extension:
+///
+/// - `GNUmakefile`, `Makefile`, `makefile`, `*.mak`, and `*.mk` are Makefiles,
+/// - `*.markdown`, `*.md`, `*.mdown`, and `*.mkd` are Markdown files, and
+/// - `*.yaml` and `*.yml` are YAML files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Profile {
+    /// Recipes have to be indented by tabs.
+    Makefile,
+
+    /// Two trailing spaces form a hard line break, link definitions may exceed
+    /// the line width, and headings need to be surrounded by blank lines.
+    Markdown,
+
+    /// Tabs must not be used for indentation.
+    Yaml,
+}
+
+impl Profile {
+    /// Select the profile of the given file by its name or extension.
+    pub fn detect(file: &Path) -> Option<Self> {
+        let name = file.file_name()?.to_string_lossy();
+        let extension = file
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match (name.as_ref(), extension.as_str()) {
+            ("GNUmakefile" | "Makefile" | "makefile", _)
+            | (_, "mak" | "mk") => Some(Self::Makefile),
+            (_, "markdown" | "md" | "mdown" | "mkd") => Some(Self::Markdown),
+            (_, "yaml" | "yml") => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// The settings to override for files of this profile.
+    pub fn overrides(self) -> Overrides {
+        match self {
+            Self::Makefile => Overrides {
+                indent_by: Some(IndentationUnit::Tabs),
+                ..Overrides::default()
+            },
+            Self::Markdown => Overrides::default(),
+            Self::Yaml => Overrides {
+                ignore_mixed_indentation: Some(true),
+                ignore_wrong_indentation: Some(true),
+                indent_by: Some(IndentationUnit::Spaces),
+                ..Overrides::default()
+            },
+        }
+    }
+}
+
+/******************************************************************************/
@@ -0,0 +1,773 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{header::Template, IndentationUnit, Severity};
+use anstyle::AnsiColor;
+use regex::Regex;
+use unicode_width::UnicodeWidthChar;
+
+/// A single line of the file to check.
+pub struct Line<'a> {
+    /// The line break terminating this line, if any.
+    pub ending: &'a str,
+
+    /// The line number, starting at 1.
+    pub number: usize,
+
+    /// The content without the line break.
+    pub text: &'a str,
+}
+
+/// A violation of a rule found in a certain line.
+pub struct Finding {
+    /// The column of the violation, starting at 1.
+    pub column: usize,
+
+    /// The count of characters affected by the violation.
+    pub length: usize,
+
+    /// The line of the violation, starting at 1.
+    pub line: usize,
+
+    /// The description of the violation.
+    pub message: String,
+}
+
+impl Finding {
+    const fn new(line: usize, column: usize, message: String) -> Self {
+        Self {
+            column,
+            length: 1,
+            line,
+            message,
+        }
+    }
+
+    const fn spanning(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+}
+
+/// A rule checked line by line.
+pub trait Rule {
+    /// Check a line of the file.
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>);
+
+    /// The code of this rule.
+    fn code(&self) -> &str;
+
+    /// The colour to highlight the code with, depending on the difficulty of
+    /// fixing the violations.
+    fn colour(&self) -> AnsiColor;
+
+    /// Finish the checks after the last line, if any, was checked.
+    fn finish(&mut self, _last: Option<&Line>, _findings: &mut Vec<Finding>) {}
+
+    /// The severity of the violations of this rule.
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+}
+
+macro_rules! rule {
+    ( $code:literal, $colour:ident ) => {
+        fn code(&self) -> &str {
+            $code
+        }
+
+        fn colour(&self) -> AnsiColor {
+            AnsiColor::$colour
+        }
+    };
+}
+
+/// ÆC-0001:  every file needs to be terminated by a line feed.
+pub struct FinalLineFeed;
+
+impl Rule for FinalLineFeed {
+    rule!("ÆC-0001", Green);
+
+    fn check(&mut self, _: &Line, _: &mut Vec<Finding>) {}
+
+    fn finish(&mut self, last: Option<&Line>, findings: &mut Vec<Finding>) {
+        let (line, column) = match last {
+            None => (1, 1),
+            Some(line) if line.ending.is_empty() => {
+                (line.number, line.text.chars().count() + 1)
+            }
+            Some(_) => return,
+        };
+
+        findings.push(Finding::new(
+            line,
+            column,
+            "File not terminated by line feed.".to_string(),
+        ));
+    }
+}
+
+/// ÆC-0002:  files must not contain CRLFs.
+pub struct CarriageReturnLineFeed;
+
+impl Rule for CarriageReturnLineFeed {
+    rule!("ÆC-0002", Yellow);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        if line.ending == "\r\n" {
+            findings.push(Finding::new(
+                line.number,
+                line.text.chars().count() + 1,
+                format!("CRLF in line {}.", line.number),
+            ));
+        }
+    }
+}
+
+/// ÆC-0003:  lines shall have a width of at most n columns.
+pub struct LineWidth {
+    /// Whether to exempt Markdown link definitions.
+    pub link_definitions: bool,
+
+    /// The maximum line width.
+    pub maximum: usize,
+
+    /// The tab stop width.
+    pub tab_width: usize,
+}
+
+impl Rule for LineWidth {
+    rule!("ÆC-0003", Red);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        let mut exceeded = None;
+        let mut width = 0;
+
+        if self.link_definitions && is_link_definition(line.text) {
+            return;
+        }
+
+        for (column, c) in (1..).zip(line.text.chars()) {
+            width = advance(width, c, self.tab_width);
+
+            if width > self.maximum && exceeded.is_none() {
+                exceeded = Some(column);
+            }
+        }
+
+        if let Some(column) = exceeded {
+            findings.push(
+                Finding::new(
+                    line.number,
+                    column,
+                    format!(
+                        "Line {} is {} column(s) too long.",
+                        line.number,
+                        width - self.maximum
+                    ),
+                )
+                .spanning(line.text.chars().count() + 1 - column),
+            );
+        }
+    }
+}
+
+/// ÆC-0004:  trailing white space characters must be removed.
+pub struct TrailingWhiteSpace {
+    /// Whether to accept two trailing spaces as Markdown hard line break.
+    pub hard_breaks: bool,
+}
+
+impl Rule for TrailingWhiteSpace {
+    rule!("ÆC-0004", Green);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        if line.text.ends_with(char::is_whitespace)
+            && !(self.hard_breaks && is_hard_break(line.text))
+        {
+            let content = line.text.trim_end().chars().count();
+
+            findings.push(
+                Finding::new(
+                    line.number,
+                    content + 1,
+                    format!("TWS in line {}.", line.number),
+                )
+                .spanning(line.text.chars().count() - content),
+            );
+        }
+    }
+}
+
+/// ÆC-0005:  lines have to be indented by the configured unit.
+pub struct WrongIndentation {
+    /// The indentation unit.
+    pub indent_by: IndentationUnit,
+}
+
+impl Rule for WrongIndentation {
+    rule!("ÆC-0005", Green);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        let (trigger, unit) = match self.indent_by {
+            IndentationUnit::Spaces => ('\t', "tabs"),
+            IndentationUnit::Tabs => (' ', "spaces"),
+        };
+
+        if line.text.starts_with(trigger) {
+            findings.push(
+                Finding::new(
+                    line.number,
+                    1,
+                    format!("Line {} indented by {unit}.", line.number),
+                )
+                .spanning(indentation(line.text)),
+            );
+        }
+    }
+}
+
+/// ÆC-0006:  spaces and tabs must not be mixed for indentation.
+pub struct MixedIndentation;
+
+impl Rule for MixedIndentation {
+    rule!("ÆC-0006", Yellow);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        if line
+            .text
+            .split_once(|c| !char::is_whitespace(c))
+            .is_some_and(|(indentation, _)| {
+                indentation.contains('\t') && indentation.contains(' ')
+            })
+        {
+            findings.push(
+                Finding::new(
+                    line.number,
+                    1,
+                    format!(
+                        "Line {} is indented by both spaces and tabs.",
+                        line.number
+                    ),
+                )
+                .spanning(indentation(line.text)),
+            );
+        }
+    }
+}
+
+/// ÆC-0007:  within any line, there shall not be any tab character.
+pub struct TabsWithinLines;
+
+impl Rule for TabsWithinLines {
+    rule!("ÆC-0007", Yellow);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        if let Some(column) = line.text.trim().chars().position(|c| c == '\t') {
+            let indentation =
+                line.text.chars().take_while(|c| c.is_whitespace()).count();
+
+            findings.push(Finding::new(
+                line.number,
+                indentation + column + 1,
+                format!("Tabs within line {}.", line.number),
+            ));
+        }
+    }
+}
+
+/// ÆC-0008:  files must not start with a UTF-8 BOM.
+pub struct ByteOrderMark;
+
+impl Rule for ByteOrderMark {
+    rule!("ÆC-0008", Green);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        if line.number == 1 && line.text.starts_with('\u{feff}') {
+            findings.push(Finding::new(
+                1,
+                1,
+                "File starts with UTF-8 BOM.".to_string(),
+            ));
+        }
+    }
+}
+
+/// ÆC-0009:  files must not end with blank lines.
+#[derive(Default)]
+pub struct TrailingBlankLines {
+    blank: usize,
+    content: bool,
+}
+
+impl Rule for TrailingBlankLines {
+    rule!("ÆC-0009", Green);
+
+    fn check(&mut self, line: &Line, _: &mut Vec<Finding>) {
+        if line.text.trim().is_empty() {
+            self.blank += 1;
+        } else {
+            self.blank = 0;
+            self.content = true;
+        }
+    }
+
+    fn finish(&mut self, last: Option<&Line>, findings: &mut Vec<Finding>) {
+        if let Some(last) = last {
+            if self.blank > 0 && self.content {
+                findings.push(Finding::new(
+                    last.number - self.blank + 1,
+                    1,
+                    format!("{} blank line(s) at the end of file.", self.blank),
+                ));
+            }
+        }
+    }
+}
+
+/// ÆC-0010:  files must not mix CRLFs and LFs as line endings.
+#[derive(Default)]
+pub struct MixedLineEndings {
+    first: Option<bool>,
+    reported: bool,
+}
+
+impl Rule for MixedLineEndings {
+    rule!("ÆC-0010", Yellow);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        if line.ending.is_empty() || self.reported {
+            return;
+        }
+
+        let crlf = line.ending == "\r\n";
+
+        if *self.first.get_or_insert(crlf) != crlf {
+            self.reported = true;
+            findings.push(Finding::new(
+                line.number,
+                1,
+                format!("Line {} ends differently than line 1.", line.number),
+            ));
+        }
+    }
+}
+
+/// ÆC-0011:  there shall not be more than n consecutive blank lines.
+pub struct ConsecutiveBlankLines {
+    blank: usize,
+    max_blank_lines: usize,
+}
+
+impl ConsecutiveBlankLines {
+    /// Create a new instance.
+    pub const fn new(max_blank_lines: usize) -> Self {
+        Self {
+            blank: 0,
+            max_blank_lines,
+        }
+    }
+}
+
+impl Rule for ConsecutiveBlankLines {
+    rule!("ÆC-0011", Green);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        if line.text.trim().is_empty() {
+            self.blank += 1;
+        } else {
+            if self.blank > self.max_blank_lines {
+                findings.push(Finding::new(
+                    line.number - self.blank + self.max_blank_lines,
+                    1,
+                    format!(
+                        "{} blank lines in a row before line {}.",
+                        self.blank, line.number
+                    ),
+                ));
+            }
+
+            self.blank = 0;
+        }
+    }
+}
+
+/// ÆC-0012:  merge conflict markers must be removed.
+#[derive(Default)]
+pub struct MergeConflictMarkers {
+    conflict: bool,
+}
+
+impl Rule for MergeConflictMarkers {
+    rule!("ÆC-0012", Red);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        let l = line.text.trim_start_matches('\u{feff}');
+        let marker = if l.starts_with("<<<<<<< ") || l == "<<<<<<<" {
+            self.conflict = true;
+            true
+        } else if l.starts_with(">>>>>>> ") || l == ">>>>>>>" {
+            self.conflict = false;
+            true
+        } else {
+            self.conflict
+                && (l == "======="
+                    || l.starts_with("||||||| ")
+                    || l == "|||||||")
+        };
+
+        if marker {
+            findings.push(
+                Finding::new(
+                    line.number,
+                    1,
+                    format!("Merge conflict marker in line {}.", line.number),
+                )
+                .spanning(7),
+            );
+        }
+    }
+}
+
+/// ÆC-0013:  invisible and bidirectional control characters must not be used.
+pub struct InvisibleCharacters;
+
+impl Rule for InvisibleCharacters {
+    rule!("ÆC-0013", Yellow);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        for (column, c) in (1..).zip(line.text.chars()) {
+            let bom = (line.number, column, c) == (1, 1, '\u{feff}');
+
+            if !bom && is_invisible(c) {
+                findings.push(Finding::new(
+                    line.number,
+                    column,
+                    format!(
+                        "Invisible character U+{:04X} in line {}.",
+                        u32::from(c),
+                        line.number
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// ÆC-0015:  Markdown headings need to be surrounded by blank lines and to
+/// separate their text from the hashes by a space.
+#[derive(Default)]
+pub struct HeadingSpacing {
+    blank: bool,
+    code: Option<(char, usize)>,
+    front_matter: bool,
+    heading: Option<usize>,
+}
+
+impl Rule for HeadingSpacing {
+    rule!("ÆC-0015", Green);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        let text = line.text.trim_start_matches('\u{feff}');
+        let blank = text.trim().is_empty();
+
+        if let Some(heading) = self.heading.take() {
+            if !blank {
+                findings.push(Finding::new(
+                    heading,
+                    1,
+                    format!(
+                        "Heading in line {heading} not followed by a blank \
+                         line."
+                    ),
+                ));
+            }
+        }
+
+        if line.number == 1 && text == "---" {
+            self.front_matter = true;
+        } else if self.front_matter {
+            self.front_matter = text != "---" && text != "...";
+        } else if let Some((c, length)) = self.code {
+            let trimmed = text.trim();
+
+            if trimmed.len() >= length && trimmed.chars().all(|d| d == c) {
+                self.code = None;
+            }
+        } else if let Some(fence) = fence(text) {
+            self.code = Some(fence);
+        } else if let Some(hashes) = heading(text) {
+            let indentation = text.len() - text.trim_start().len();
+            let rest = &text[indentation + hashes..];
+
+            if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+                findings.push(Finding::new(
+                    line.number,
+                    indentation + hashes + 1,
+                    format!(
+                        "No space after the hashes of heading in line {}.",
+                        line.number
+                    ),
+                ));
+            }
+
+            if line.number > 1 && !self.blank {
+                findings.push(Finding::new(
+                    line.number,
+                    1,
+                    format!(
+                        "Heading in line {} not preceded by a blank line.",
+                        line.number
+                    ),
+                ));
+            }
+
+            self.heading = Some(line.number);
+        }
+
+        self.blank = blank;
+    }
+}
+
+/// ÆC-0016:  YAML files must not be indented by tabs.
+pub struct YamlTabIndentation;
+
+impl Rule for YamlTabIndentation {
+    rule!("ÆC-0016", Red);
+
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        let indentation = line.text.len() - line.text.trim_start().len();
+
+        if let Some(column) =
+            line.text[..indentation].chars().position(|c| c == '\t')
+        {
+            findings.push(Finding::new(
+                line.number,
+                column + 1,
+                format!("Line {} of YAML file indented by tabs.", line.number),
+            ));
+        }
+    }
+}
+
+/// ÆC-0014:  files have to start with the configured license header.
+pub struct LicenseHeader {
+    lines: Vec<String>,
+    offset: usize,
+    template: Template,
+}
+
+impl LicenseHeader {
+    /// Create a new instance.
+    pub const fn new(template: Template) -> Self {
+        Self {
+            lines: Vec::new(),
+            offset: 0,
+            template,
+        }
+    }
+}
+
+impl Rule for LicenseHeader {
+    rule!("ÆC-0014", Yellow);
+
+    fn check(&mut self, line: &Line, _: &mut Vec<Finding>) {
+        let text = line.text.trim_start_matches('\u{feff}');
+
+        if line.number == 1 && text.starts_with("#!") {
+            self.offset = 1;
+        } else if self.lines.len() < self.template.len() {
+            self.lines.push(text.to_string());
+        }
+    }
+
+    fn finish(&mut self, _: Option<&Line>, findings: &mut Vec<Finding>) {
+        let lines = self.lines.iter().map(String::as_str).collect::<Vec<_>>();
+
+        if let Some((line, column, message)) = self.template.check(&lines) {
+            findings.push(Finding::new(line + self.offset, column, message));
+        }
+    }
+}
+
+/// A rule defined by the user by a regular expression.
+///
+/// Each match of the expression within a line is a violation.
+#[derive(Clone, Debug)]
+pub struct Custom {
+    /// The colour to highlight the identifier with.
+    pub colour: AnsiColor,
+
+    /// The identifier of this rule.
+    pub id: String,
+
+    /// The description of the violations.
+    pub message: String,
+
+    /// The pattern to search for.
+    pub regex: Regex,
+
+    /// The severity of the violations.
+    pub severity: Severity,
+}
+
+impl Rule for Custom {
+    fn check(&mut self, line: &Line, findings: &mut Vec<Finding>) {
+        for m in self.regex.find_iter(line.text) {
+            findings.push(
+                Finding::new(
+                    line.number,
+                    line.text[..m.start()].chars().count() + 1,
+                    self.message.clone(),
+                )
+                .spanning(m.as_str().chars().count()),
+            );
+        }
+    }
+
+    fn code(&self) -> &str {
+        &self.id
+    }
+
+    fn colour(&self) -> AnsiColor {
+        self.colour
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+/// Check whether the given Markdown line is a link reference definition.
+fn is_link_definition(text: &str) -> bool {
+    let trimmed = text.trim_start();
+
+    text.len() - trimmed.len() <= 3
+        && trimmed.starts_with('[')
+        && trimmed.find("]:").is_some_and(|i| i > 1)
+}
+
+/// Advance the given display column by the given character.
+pub fn advance(column: usize, c: char, tab_width: usize) -> usize {
+    if c == '\t' {
+        column + tab_width - column % tab_width
+    } else {
+        column + c.width().unwrap_or_default()
+    }
+}
+
+/// Check the given data against the given rules in a single pass.
+///
+/// The findings are returned grouped by the rules, in the order of the rules.
+pub fn check(data: &str, rules: &mut [Box<dyn Rule>]) -> Vec<Vec<Finding>> {
+    let mut findings = rules.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+    let mut last = None;
+
+    for line in lines(data) {
+        for (rule, findings) in rules.iter_mut().zip(findings.iter_mut()) {
+            rule.check(&line, findings);
+        }
+
+        last = Some(line);
+    }
+
+    for (rule, findings) in rules.iter_mut().zip(findings.iter_mut()) {
+        rule.finish(last.as_ref(), findings);
+    }
+
+    findings
+}
+
+/// Split the given data into lines.
+pub fn lines(data: &str) -> impl Iterator<Item = Line<'_>> {
+    (1..)
+        .zip(data.split_inclusive('\n'))
+        .map(|(number, piece)| {
+            let (text, ending) = piece.strip_suffix("\r\n").map_or_else(
+                || {
+                    piece
+                        .strip_suffix('\n')
+                        .map_or((piece, ""), |text| (text, "\n"))
+                },
+                |text| (text, "\r\n"),
+            );
+
+            Line {
+                ending,
+                number,
+                text,
+            }
+        })
+}
+
+/// Check whether the given Markdown line opens a code fence and return the
+/// character and length of the fence.
+fn fence(text: &str) -> Option<(char, usize)> {
+    let trimmed = text.trim_start();
+
+    if text.len() - trimmed.len() > 3 {
+        return None;
+    }
+
+    ['`', '~'].into_iter().find_map(|c| {
+        let count = trimmed.chars().take_while(|&d| d == c).count();
+
+        (count >= 3).then_some((c, count))
+    })
+}
+
+/// Count the characters of the indentation of the given line.
+fn indentation(text: &str) -> usize {
+    text.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+/// Check whether the given Markdown line is an ATX heading and return the
+/// count of its hashes.
+fn heading(text: &str) -> Option<usize> {
+    let trimmed = text.trim_start_matches(' ');
+    let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+
+    (text.len() - trimmed.len() <= 3
+        && (1..=6).contains(&hashes)
+        && trimmed[hashes..].chars().next().is_none_or(|c| c != '#'))
+    .then_some(hashes)
+}
+
+/// Check whether the given Markdown line ends with a hard line break.
+pub fn is_hard_break(text: &str) -> bool {
+    text.ends_with("  ") && !text.ends_with("   ") && !text.trim().is_empty()
+}
+
+/// Check whether the given character is invisible or controls the text
+/// direction.
+const fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00ad}'
+            | '\u{061c}'
+            | '\u{180e}'
+            | '\u{200b}'..='\u{200f}'
+            | '\u{202a}'..='\u{202e}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{feff}'
+    )
+}
+
+/******************************************************************************/
@@ -0,0 +1,150 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{edits::Edits, release::Release};
+use crate::ceprintlns;
+use aeruginous_io::PathBufLikeReader;
+use std::path::{Path, PathBuf};
+use sysexits::{ExitCode, Result};
+
+/// The placeholder for the major version.
+const MAJOR: &str = "{major}";
+
+/// The placeholder for the minor version.
+const MINOR: &str = "{minor}";
+
+/// The placeholder for the patch level.
+const PATCH: &str = "{patch}";
+
+/// The placeholder for the entire version.
+const VERSION: &str = "{version}";
+
+/// The further locations of the version to update.
+///
+/// Each location names a file, relative to the configuration file, as well as
+/// a search template and an optional replacement template, defaulting to the
+/// search template.  The templates may contain the placeholders `{major}`,
+/// `{minor}`, `{patch}`, and `{version}`, including any pre-release identifier,
+/// which are filled with the old version for searching and with the new version
+/// for replacing.
+///
+/// ```toml
+/// [[file]]
+/// path = "src/lib.rs"
+/// search = 'pub const VERSION: &str = "v{version}";'
+///
+/// [[file]]
+/// path = "Dockerfile"
+/// search = "LABEL version={version}"
+/// ```
+pub struct Config {
+    locations: Vec<Location>,
+}
+
+impl Config {
+    /// Load the configuration from the given file.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::Config`]
+    /// - See [`aeruginous_io::PathBufLikeReader::read_loudly`].
+    pub fn load(path: &Path) -> Result<Self> {
+        let file: File = toml::from_str(&path.read_loudly()?).map_err(|e| {
+            eprintln!("{}:  {e}", path.display());
+            ExitCode::Config
+        })?;
+        let root = path.parent().unwrap_or_else(|| Path::new(""));
+
+        Ok(Self {
+            locations: file
+                .file
+                .into_iter()
+                .map(|location| Location {
+                    path: root.join(location.path),
+                    ..location
+                })
+                .collect(),
+        })
+    }
+
+    /// Update all locations from the old to the new version.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::DataErr`]
+    /// - See [`Edits::get`].
+    pub fn update(
+        &self,
+        old: &Release,
+        new: &Release,
+        edits: &mut Edits,
+    ) -> Result<()> {
+        for location in &self.locations {
+            let search = render(&location.search, old);
+            let replace = render(
+                location.replace.as_ref().unwrap_or(&location.search),
+                new,
+            );
+            let data = edits.get(&location.path)?;
+
+            if !data.contains(&search) {
+                ceprintlns!(
+                    "Pattern"!Red,
+                    "`{search}` not found in {}.",
+                    location.path.display()
+                );
+                return Err(ExitCode::DataErr);
+            }
+
+            *data = data.replace(&search, &replace);
+        }
+
+        Ok(())
+    }
+}
+
+/// The contents of a configuration file.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    #[serde(default)]
+    file: Vec<Location>,
+}
+
+/// A location of the version within a certain file.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Location {
+    path: PathBuf,
+    replace: Option<String>,
+    search: String,
+}
+
+/// Fill the placeholders of the given template with the given version.
+pub fn render(template: &str, release: &Release) -> String {
+    let version = release.version();
+
+    template
+        .replace(MAJOR, &version.major().to_string())
+        .replace(MINOR, &version.minor().to_string())
+        .replace(PATCH, &version.patch().to_string())
+        .replace(VERSION, &release.to_string())
+}
+
+/******************************************************************************/
@@ -0,0 +1,832 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use super::{
+    config::{render, Config},
+    edits::Edits,
+    git::Git,
+    manifest::{value_of, Manifest},
+    release::{Range, Release},
+};
+use crate::{
+    ceprintlns, AppendAsLine, Ronlog, RonlogSection, ToMd, VersionRange,
+};
+use aeruginous_io::PathBufLikeReader;
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use sysexits::{ExitCode, Result};
+
+/// The default RONLOG to detect the old version from.
+const CHANGELOG: &str = "CHANGELOG.ron";
+
+/// The default template of the commit message.
+const MESSAGE: &str = "Bump version to v{version}";
+
+/// Increment a hard-coded version string in some files.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(clap::Parser, Clone)]
+#[command(visible_aliases = ["incver", "inc-ver", "incrementversion"])]
+pub struct IncrementVersion {
+    /// The RONLOG to detect the old version from, if not specified, defaulting
+    /// to `CHANGELOG.ron`.
+    #[arg(long)]
+    changelog: Option<PathBuf>,
+
+    /// Only preview the edits as unified diff and fail if any file would
+    /// change.
+    #[arg(long)]
+    check: bool,
+
+    /// Commit the edited files.
+    #[arg(conflicts_with_all = ["check", "dry_run"], long)]
+    commit: bool,
+
+    /// The configuration file listing further locations of the version.
+    #[arg(long, short)]
+    config: Option<PathBuf>,
+
+    /// Only preview the edits as unified diff.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// The files to work on.
+    #[arg(long = "edit", short = 'e')]
+    file_to_edit: Vec<PathBuf>,
+
+    /// The files to update and reformat.
+    #[arg(long = "rewrite", short = 'R')]
+    file_to_rewrite: Vec<PathBuf>,
+
+    /// Commit even if the working tree should have uncommitted changes.
+    #[arg(long, requires = "commit")]
+    force: bool,
+
+    /// The template of the commit message, defaulting to `Bump version to
+    /// v{version}`.
+    ///
+    /// The placeholders are the same as for the configuration file.
+    #[arg(long, requires = "commit", short)]
+    message: Option<String>,
+
+    /// The old version to search for and replace.
+    ///
+    /// If omitted, the version is detected from the given `Cargo.toml` and
+    /// `CITATION.cff` files as well as from the newest section of the RONLOG.
+    #[arg(long, short = 'v')]
+    old_version: Option<String>,
+
+    /// In case of Rust projects:  which package's version shall be edited?
+    #[arg(long, short)]
+    package: Option<String>,
+
+    /// The identifier to number pre-releases by, defaulting to `rc`.
+    #[arg(long)]
+    preid: Option<String>,
+
+    /// The increment range.
+    #[arg(long, required_unless_present = "set", short)]
+    range: Option<Range>,
+
+    /// The version to set instead of incrementing the old one.
+    #[arg(conflicts_with = "range", long)]
+    set: Option<String>,
+
+    /// Create an annotated tag for the commit.
+    ///
+    /// The tag message is taken from the matching section of the RONLOG, if
+    /// any, and from the commit message, otherwise.
+    #[arg(long, requires = "commit")]
+    tag: bool,
+}
+
+impl IncrementVersion {
+    /// Set the RONLOG to detect the old version from, if not specified.
+    pub fn changelog<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.changelog = Some(PathBuf::from(path));
+    }
+
+    /// Only preview the edits as unified diff and fail if any file would
+    /// change.
+    pub const fn check(&mut self) {
+        self.check = true;
+    }
+
+    /// Commit the edited files.
+    pub const fn commit(&mut self) {
+        self.commit = true;
+    }
+
+    /// Set the configuration file listing further locations of the version.
+    pub fn config<T>(&mut self, path: T)
+    where
+        PathBuf: From<T>,
+    {
+        self.config = Some(PathBuf::from(path));
+    }
+
+    /// Detect the old version instead of using the given one.
+    pub fn detect_old_version(&mut self) {
+        self.old_version = None;
+    }
+
+    /// Only preview the edits as unified diff.
+    pub const fn dry_run(&mut self) {
+        self.dry_run = true;
+    }
+
+    /// Commit even if the working tree should have uncommitted changes.
+    pub const fn force(&mut self) {
+        self.force = true;
+    }
+
+    /// Process the input data.
+    ///
+    /// # Errors
+    ///
+    /// See [`sysexits::ExitCode`].
+    pub fn main(&self) -> Result<()> {
+        self.wrap().main()
+    }
+
+    /// Set the template of the commit message.
+    pub fn message<T>(&mut self, template: T)
+    where
+        String: From<T>,
+    {
+        self.message = Some(String::from(template));
+    }
+
+    /// Construct a new instance.
+    #[must_use]
+    pub const fn new(
+        file_to_edit: Vec<PathBuf>,
+        file_to_rewrite: Vec<PathBuf>,
+        old_version: String,
+        package: Option<String>,
+        range: VersionRange,
+    ) -> Self {
+        Self {
+            changelog: None,
+            check: false,
+            commit: false,
+            config: None,
+            dry_run: false,
+            file_to_edit,
+            file_to_rewrite,
+            force: false,
+            message: None,
+            old_version: Some(old_version),
+            package,
+            preid: None,
+            range: Some(Range::from_version_range(range)),
+            set: None,
+            tag: false,
+        }
+    }
+
+    /// Set the identifier to number pre-releases by.
+    pub fn preid<T>(&mut self, identifier: T)
+    where
+        String: From<T>,
+    {
+        self.preid = Some(String::from(identifier));
+    }
+
+    /// Set the increment range, including the pre-release ones.
+    pub const fn range(&mut self, range: Range) {
+        self.range = Some(range);
+    }
+
+    /// Set an explicit version instead of incrementing the old one.
+    pub fn set<T>(&mut self, version: T)
+    where
+        String: From<T>,
+    {
+        self.set = Some(String::from(version));
+    }
+
+    /// Create an annotated tag for the commit.
+    pub const fn tag(&mut self) {
+        self.tag = true;
+    }
+
+    fn wrap(&self) -> Logic {
+        Logic {
+            cli: self.clone(),
+            new_version: String::new(),
+            old_version: self
+                .old_version
+                .as_deref()
+                .map_or_else(String::new, |v| {
+                    v.strip_prefix('v').unwrap_or(v).to_string()
+                }),
+        }
+    }
+}
+
+struct Logic {
+    cli: IncrementVersion,
+    new_version: String,
+    old_version: String,
+}
+
+impl Logic {
+    fn changelog(&self) -> &Path {
+        self.cli
+            .changelog
+            .as_deref()
+            .unwrap_or_else(|| Path::new(CHANGELOG))
+    }
+
+    fn detect_old_version(&mut self) -> Result<()> {
+        let mut files = self
+            .cli
+            .file_to_edit
+            .iter()
+            .chain(&self.cli.file_to_rewrite)
+            .collect::<Vec<_>>();
+        let mut found = Vec::new();
+
+        files.sort();
+        files.dedup();
+
+        for file in files {
+            let version = match (
+                file.file_name().and_then(std::ffi::OsStr::to_str),
+                file.extension().and_then(std::ffi::OsStr::to_str),
+            ) {
+                (Some("Cargo.toml"), _) => self.detect_in_cargo_toml(file)?,
+                (_, Some("cff")) => Self::detect_in_citation_cff(file)?,
+                _ => None,
+            };
+
+            if let Some(version) = version {
+                found.push((file.clone(), version));
+            }
+        }
+
+        if let Some(section) = self.sections()?.iter().max() {
+            let version = section.version();
+
+            found.push((
+                self.changelog().to_path_buf(),
+                format!(
+                    "{}.{}.{}",
+                    version.major(),
+                    version.minor(),
+                    version.patch()
+                ),
+            ));
+        }
+
+        let Some((_, version)) = found.first() else {
+            ceprintlns!(
+                "Version"!Red,
+                "not detectable, please specify `--old-version`."
+            );
+            return Err(ExitCode::Usage);
+        };
+
+        if found.iter().any(|(_, v)| v != version) {
+            ceprintlns!("Version"!Red, "ambiguous, the sources disagree:");
+
+            for (file, version) in &found {
+                eprintln!("  - {version} in {}", file.display());
+            }
+
+            return Err(ExitCode::DataErr);
+        }
+
+        self.old_version.clone_from(version);
+        Ok(())
+    }
+
+    fn detect_in_cargo_toml(&self, file: &Path) -> Result<Option<String>> {
+        let Ok(manifest) = file.read_loudly()?.parse::<toml::Table>() else {
+            ceprintlns!("Cargo.toml"!Red, "does not seem to be valid TOML.");
+            return Err(ExitCode::DataErr);
+        };
+        let package = manifest.get("package").filter(|p| {
+            self.selects(
+                p.get("name")
+                    .and_then(toml::Value::as_str)
+                    .unwrap_or_default(),
+            )
+        });
+        let workspace =
+            manifest.get("workspace").and_then(|w| w.get("package"));
+
+        Ok(package
+            .and_then(|p| p.get("version"))
+            .and_then(toml::Value::as_str)
+            .or_else(|| {
+                workspace
+                    .and_then(|w| w.get("version"))
+                    .and_then(toml::Value::as_str)
+            })
+            .map(|v| v.strip_prefix('v').unwrap_or(v).to_string()))
+    }
+
+    fn detect_in_citation_cff(file: &Path) -> Result<Option<String>> {
+        Ok(file.read_loudly()?.lines().find_map(|line| {
+            let version = line
+                .strip_prefix("version:")?
+                .trim()
+                .trim_matches(['"', '\'']);
+
+            Some(version.strip_prefix('v').unwrap_or(version).to_string())
+        }))
+    }
+
+    fn determine_new_version(&mut self) -> Result<()> {
+        self.new_version = if let Some(version) = &self.cli.set {
+            Release::from_str(version.strip_prefix('v').unwrap_or(version))?
+        } else {
+            Release::from_str(&self.old_version)?.increment(
+                self.cli.range.ok_or(ExitCode::Usage)?,
+                self.cli.preid.as_deref().unwrap_or("rc"),
+            )
+        }
+        .to_string();
+
+        Ok(())
+    }
+
+    fn edit(&self, file: &Path, data: &str, rewrite: bool) -> Result<String> {
+        if let Some(manifest) = Manifest::detect(file) {
+            return self.edit_manifest(file, data, manifest);
+        }
+
+        match (
+            file.file_name()
+                .ok_or(ExitCode::Usage)?
+                .to_str()
+                .ok_or(ExitCode::DataErr)?,
+            file.extension().and_then(std::ffi::OsStr::to_str),
+        ) {
+            ("Cargo.lock", _) => self.edit_cargo_lock(data),
+            ("Cargo.toml", _) if rewrite => self.rewrite_cargo_toml(data),
+            ("Cargo.toml", _) => Ok(self.edit_cargo_toml(data)),
+            (_, Some("cff")) => Ok(self.edit_citation_cff(data)),
+            _ => Ok(self.edit_normal_file(data)),
+        }
+    }
+
+    fn edit_cargo_lock(&self, data: &str) -> Result<String> {
+        let old = cargo_lock::Version::parse(&self.old_release()?);
+        let new = cargo_lock::Version::parse(&self.new_version);
+        let (Ok(old), Ok(new)) = (old, new) else {
+            ceprintlns!("Version"!Red, "not applicable to `Cargo.lock`.");
+            return Err(ExitCode::DataErr);
+        };
+        let mut lock_file = data
+            .parse::<cargo_lock::Lockfile>()
+            .map_err(|_| ExitCode::Unavailable)?;
+        let mut edited = Vec::new();
+
+        for package in &mut lock_file.packages {
+            if package.source.is_none()
+                && package.version == old
+                && self.selects(package.name.as_str())
+            {
+                package.version = new.clone();
+                edited.push(package.name.clone());
+            }
+        }
+
+        for package in &mut lock_file.packages {
+            for dependency in &mut package.dependencies {
+                if dependency.source.is_none()
+                    && dependency.version == old
+                    && edited.contains(&dependency.name)
+                {
+                    dependency.version = new.clone();
+                }
+            }
+        }
+
+        if edited.is_empty() {
+            ceprintlns!("Package"!Red, "not found.");
+            Err(ExitCode::DataErr)
+        } else {
+            Ok(lock_file.to_string())
+        }
+    }
+
+    fn edit_cargo_table(&self, lines: &[&str], buffer: &mut String) {
+        let header = lines.first().filter(|l| l.trim_start().starts_with('['));
+        let keys = header.map_or_else(Vec::new, |header| {
+            header
+                .trim()
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split('.')
+                .map(|key| key.trim().trim_matches(['"', '\'']))
+                .collect::<Vec<_>>()
+        });
+        let mut version_updated = false;
+
+        match keys.as_slice() {
+            ["package"] | ["workspace", "package"] => {
+                for line in lines {
+                    if line.starts_with("version")
+                        && line.contains(&self.old_version)
+                        && !version_updated
+                    {
+                        version_updated = true;
+                        buffer.append_as_line(
+                            line.replace(&self.old_version, &self.new_version),
+                        );
+                    } else {
+                        buffer.append_as_line(*line);
+                    }
+                }
+            }
+            [.., kind] if is_dependency_table(kind) => {
+                for line in lines {
+                    let name = line
+                        .split_once('=')
+                        .map(|(name, _)| name.trim().trim_matches(['"', '\'']));
+
+                    buffer.append_as_line(
+                        name.filter(|name| {
+                            self.selects(name)
+                                && value_of(line, "path").is_some()
+                        })
+                        .and_then(|_| self.update_requirement(line))
+                        .unwrap_or_else(|| (*line).to_string()),
+                    );
+                }
+            }
+            [.., kind, name]
+                if is_dependency_table(kind)
+                    && self.selects(name)
+                    && lines.iter().any(|l| value_of(l, "path").is_some()) =>
+            {
+                for line in lines {
+                    buffer.append_as_line(
+                        self.update_requirement(line)
+                            .unwrap_or_else(|| (*line).to_string()),
+                    );
+                }
+            }
+            _ => {
+                for line in lines {
+                    buffer.append_as_line(*line);
+                }
+            }
+        }
+    }
+
+    fn edit_cargo_toml(&self, data: &str) -> String {
+        let mut buffer = String::new();
+        let mut table = Vec::new();
+
+        for line in data.lines() {
+            if line.trim_start().starts_with('[') {
+                self.edit_cargo_table(&table, &mut buffer);
+                table.clear();
+            }
+
+            table.push(line);
+        }
+
+        self.edit_cargo_table(&table, &mut buffer);
+        buffer
+    }
+
+    fn edit_citation_cff(&self, data: &str) -> String {
+        let mut buffer = String::new();
+
+        for line in data.lines() {
+            if line.starts_with("version:") {
+                buffer.append_as_line(
+                    line.replace(&self.old_version, &self.new_version),
+                );
+            } else if line.starts_with("date-released:") {
+                buffer.append_as_line(format!(
+                    "date-released: {}",
+                    chrono::Local::now().format("%Y-%m-%d")
+                ));
+            } else {
+                buffer.append_as_line(line);
+            }
+        }
+
+        buffer
+    }
+
+    fn edit_manifest(
+        &self,
+        file: &Path,
+        data: &str,
+        manifest: Manifest,
+    ) -> Result<String> {
+        let ranges = manifest.locate(data);
+        let mut buffer = String::new();
+        let mut last = 0;
+
+        if ranges.is_empty() {
+            ceprintlns!(
+                "Version"!Red,
+                "field `{}` not found in {}.",
+                manifest.field(),
+                file.display()
+            );
+            return Err(ExitCode::DataErr);
+        }
+
+        for range in ranges {
+            buffer.push_str(&data[last..range.start]);
+            buffer.push_str(
+                &data[range.clone()]
+                    .replace(&self.old_version, &self.new_version),
+            );
+            last = range.end;
+        }
+
+        buffer.push_str(&data[last..]);
+        Ok(buffer)
+    }
+
+    fn edit_normal_file(&self, data: &str) -> String {
+        data.replace(&self.old_version, &self.new_version)
+    }
+
+    fn main(&mut self) -> Result<()> {
+        let mut edits = Edits::default();
+        let git = if self.cli.commit {
+            Some(Git::open(self.repository(), self.cli.force)?)
+        } else {
+            None
+        };
+
+        if self.old_version.is_empty() {
+            self.detect_old_version()?;
+        }
+
+        self.determine_new_version()?;
+
+        let notes = if self.cli.tag {
+            if let Some(git) = &git {
+                git.ensure_tag_free(&self.tag_name())?;
+            }
+
+            self.release_notes()?
+        } else {
+            None
+        };
+
+        for file in &self.cli.file_to_edit {
+            let data = edits.get(file)?;
+            *data = self.edit(file, data, false)?;
+        }
+
+        for file in &self.cli.file_to_rewrite {
+            let data = edits.get(file)?;
+            *data = self.edit(file, data, true)?;
+        }
+
+        if let Some(config) = &self.cli.config {
+            Config::load(config)?.update(
+                &Release::from_str(&self.old_version)?,
+                &Release::from_str(&self.new_version)?,
+                &mut edits,
+            )?;
+        }
+
+        if self.cli.check || self.cli.dry_run {
+            if edits.diff() > 0 && self.cli.check {
+                Err(ExitCode::DataErr)
+            } else {
+                Ok(())
+            }
+        } else {
+            edits.write()?;
+
+            if let Some(git) = git {
+                self.record(&git, &edits, notes)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn new_requirement(&self, requirement: &str) -> Option<String> {
+        let version = requirement.trim_start_matches(['<', '=', '>', '^', '~']);
+        let operator = &requirement[..requirement.len() - version.len()];
+        let version = version.trim();
+        let old_release = self.old_release().ok()?;
+        let parts = version.split('.').collect::<Vec<_>>();
+        let old = old_release.split(['-', '.']).collect::<Vec<_>>();
+        let new = self.new_version.split('.').collect::<Vec<_>>();
+
+        if version == old_release || self.new_version.contains('-') {
+            (version == old_release || parts == old[..parts.len().min(3)])
+                .then(|| format!("{operator}{}", self.new_version))
+        } else {
+            (parts.len() <= 3 && parts == old[..parts.len()])
+                .then(|| format!("{operator}{}", new[..parts.len()].join(".")))
+        }
+    }
+
+    fn old_release(&self) -> Result<String> {
+        Ok(Release::from_str(&self.old_version)?.to_string())
+    }
+
+    fn record(
+        &self,
+        git: &Git,
+        edits: &Edits,
+        notes: Option<String>,
+    ) -> Result<()> {
+        let release = Release::from_str(&self.new_version)?;
+        let message =
+            render(self.cli.message.as_deref().unwrap_or(MESSAGE), &release);
+        let commit = git.commit(&edits.paths(), &message)?;
+
+        if self.cli.tag {
+            git.tag(&self.tag_name(), commit, &notes.unwrap_or(message))?;
+        }
+
+        Ok(())
+    }
+
+    fn release_notes(&self) -> Result<Option<String>> {
+        let release = Release::from_str(&self.new_version)?;
+
+        if release.is_pre_release() {
+            return Ok(None);
+        }
+
+        let Some(section) = self
+            .sections()?
+            .into_iter()
+            .find(|section| *section.version() == release.version())
+        else {
+            return Ok(None);
+        };
+        let mut notes = section.introduction().clone().unwrap_or_default();
+
+        notes.push_str("\n\n");
+        notes.push_str(&section.changes().to_md(3)?);
+
+        let notes = notes.trim();
+
+        Ok((!notes.is_empty()).then(|| format!("{notes}\n")))
+    }
+
+    fn repository(&self) -> &Path {
+        self.cli
+            .file_to_edit
+            .iter()
+            .chain(&self.cli.file_to_rewrite)
+            .next()
+            .and_then(|file| file.parent())
+            .filter(|directory| !directory.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+    }
+
+    fn rewrite_cargo_toml(&self, data: &str) -> Result<String> {
+        let Ok(mut manifest) = data.parse::<toml::Table>() else {
+            ceprintlns!("Cargo.toml"!Red, "does not seem to be valid TOML.");
+            return Err(ExitCode::DataErr);
+        };
+        let mut versioned = self.rewrite_version(manifest.get_mut("package"));
+
+        self.rewrite_dependencies(&mut manifest);
+
+        if let Some(workspace) = manifest
+            .get_mut("workspace")
+            .and_then(toml::Value::as_table_mut)
+        {
+            versioned |= self.rewrite_version(workspace.get_mut("package"));
+            self.rewrite_dependencies(workspace);
+        }
+
+        if versioned {
+            Ok(manifest.to_string())
+        } else {
+            ceprintlns!(
+                "Cargo.toml"!Red,
+                "does not contain a `package.version` field."
+            );
+
+            Err(ExitCode::DataErr)
+        }
+    }
+
+    fn rewrite_dependencies(&self, table: &mut toml::Table) {
+        for (kind, dependencies) in table.iter_mut() {
+            let Some(dependencies) = dependencies.as_table_mut() else {
+                continue;
+            };
+
+            if kind == "target" {
+                for (_, target) in dependencies.iter_mut() {
+                    if let Some(target) = target.as_table_mut() {
+                        self.rewrite_dependencies(target);
+                    }
+                }
+            } else if is_dependency_table(kind) {
+                for (name, dependency) in dependencies.iter_mut() {
+                    let Some(dependency) = dependency.as_table_mut() else {
+                        continue;
+                    };
+
+                    if self.selects(name) && dependency.contains_key("path") {
+                        if let Some(requirement) = dependency
+                            .get("version")
+                            .and_then(toml::Value::as_str)
+                            .and_then(|r| self.new_requirement(r))
+                        {
+                            dependency
+                                .insert("version".into(), requirement.into());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn rewrite_version(&self, package: Option<&mut toml::Value>) -> bool {
+        match package.and_then(|p| p.get_mut("version")) {
+            Some(version) if version.is_str() => {
+                *version = self.new_version.clone().into();
+                true
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn sections(&self) -> Result<Vec<RonlogSection>> {
+        let changelog = self.changelog();
+
+        if !changelog.is_file() {
+            return Ok(Vec::new());
+        }
+
+        match Ronlog::sections(changelog) {
+            Err(ExitCode::DataErr) => {
+                ceprintlns!(
+                    "RONLOG"!Red,
+                    "{} does not seem to be valid.",
+                    changelog.display()
+                );
+                Err(ExitCode::DataErr)
+            }
+            result => result,
+        }
+    }
+
+    fn selects(&self, package: &str) -> bool {
+        self.cli.package.as_ref().is_none_or(|name| name == package)
+    }
+
+    fn tag_name(&self) -> String {
+        format!("v{}", self.new_version)
+    }
+
+    fn update_requirement(&self, line: &str) -> Option<String> {
+        let range = value_of(line, "version")?;
+        let requirement = self.new_requirement(&line[range.clone()])?;
+
+        Some(format!(
+            "{}{requirement}{}",
+            &line[..range.start],
+            &line[range.end..]
+        ))
+    }
+}
+
+/// Whether the given key names a table of dependencies.
+fn is_dependency_table(key: &str) -> bool {
+    matches!(
+        key,
+        "build-dependencies" | "dependencies" | "dev-dependencies"
+    )
+}
+
+/******************************************************************************/
@@ -0,0 +1,110 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use aeruginous_io::{PathBufLikeReader, PathBufLikeTruncation};
+use std::path::{Path, PathBuf};
+use sysexits::Result;
+
+/// The files to edit with both their original and their edited contents.
+///
+/// The edits are only kept in memory until they are either written or
+/// previewed such that a file can be edited multiple times and nothing is
+/// written if any edit should fail.
+#[derive(Default)]
+pub struct Edits {
+    files: Vec<Edit>,
+}
+
+impl Edits {
+    /// Print a unified diff for each file to be changed.
+    ///
+    /// The count of files to be changed is returned.
+    pub fn diff(&self) -> usize {
+        let mut count = 0;
+
+        for file in self.files.iter().filter(|f| f.edited != f.original) {
+            let path = file.path.display().to_string();
+
+            print!(
+                "{}",
+                similar::TextDiff::from_lines(&file.original, &file.edited)
+                    .unified_diff()
+                    .header(&path, &path)
+            );
+            count += 1;
+        }
+
+        count
+    }
+
+    /// The edited contents of the given file, reading it on first access.
+    ///
+    /// # Errors
+    ///
+    /// See [`aeruginous_io::PathBufLikeReader::read_loudly`].
+    pub fn get(&mut self, path: &Path) -> Result<&mut String> {
+        let index = if let Some(index) =
+            self.files.iter().position(|f| f.path == path)
+        {
+            index
+        } else {
+            let original = path.read_loudly()?;
+
+            self.files.push(Edit {
+                edited: original.clone(),
+                original,
+                path: path.to_path_buf(),
+            });
+            self.files.len() - 1
+        };
+
+        Ok(&mut self.files[index].edited)
+    }
+
+    /// The paths of all files to be changed.
+    pub fn paths(&self) -> Vec<&Path> {
+        self.files
+            .iter()
+            .filter(|f| f.edited != f.original)
+            .map(|f| f.path.as_path())
+            .collect()
+    }
+
+    /// Write all files to be changed.
+    ///
+    /// # Errors
+    ///
+    /// See [`aeruginous_io::PathBufLikeTruncation::truncate_loudly`].
+    pub fn write(&self) -> Result<()> {
+        for file in self.files.iter().filter(|f| f.edited != f.original) {
+            file.edited.clone().truncate_loudly(&file.path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The contents of a single file, before and after editing.
+struct Edit {
+    edited: String,
+    original: String,
+    path: PathBuf,
+}
+
+/******************************************************************************/
@@ -0,0 +1,194 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use crate::ceprintlns;
+use git2::{Index, Oid, Repository, Signature, StatusOptions};
+use std::path::{Path, PathBuf};
+use sysexits::{ExitCode, Result};
+
+/// The Git repository to record a version bump in.
+pub struct Git {
+    repository: Repository,
+}
+
+impl Git {
+    /// Stage the given files and commit them on top of `HEAD`.
+    ///
+    /// The commit only consists of `HEAD`'s tree and the given files such that
+    /// any other staged changes are neither committed nor unstaged.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::Config`]
+    /// - [`sysexits::ExitCode::DataErr`]
+    /// - [`sysexits::ExitCode::Unavailable`]
+    /// - [`sysexits::ExitCode::Usage`]
+    pub fn commit(&self, paths: &[&Path], message: &str) -> Result<Oid> {
+        let root = self.root()?;
+        let mut index = self.repository.index().map_err(|e| unavailable(&e))?;
+        let mut staged = Index::new().map_err(|e| unavailable(&e))?;
+        let parent = self
+            .repository
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .ok();
+
+        if let Some(parent) = &parent {
+            parent
+                .tree()
+                .and_then(|tree| staged.read_tree(&tree))
+                .map_err(|e| unavailable(&e))?;
+        }
+
+        for path in paths {
+            let Some(relative) = path.canonicalize().ok().and_then(|p| {
+                p.strip_prefix(&root).ok().map(Path::to_path_buf)
+            }) else {
+                ceprintlns!(
+                    "File"!Red,
+                    "{} is not within the repository.",
+                    path.display()
+                );
+                return Err(ExitCode::DataErr);
+            };
+
+            index.add_path(&relative).map_err(|e| unavailable(&e))?;
+
+            if let Some(entry) = index.get_path(&relative, 0) {
+                staged.add(&entry).map_err(|e| unavailable(&e))?;
+            }
+        }
+
+        index.write().map_err(|e| unavailable(&e))?;
+
+        let tree = staged
+            .write_tree_to(&self.repository)
+            .and_then(|oid| self.repository.find_tree(oid))
+            .map_err(|e| unavailable(&e))?;
+        let signature = self.signature()?;
+
+        self.repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parent.iter().collect::<Vec<_>>(),
+            )
+            .map_err(|e| unavailable(&e))
+    }
+
+    /// Ensure that the given tag does not exist, yet.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::DataErr`]
+    pub fn ensure_tag_free(&self, name: &str) -> Result<()> {
+        if self
+            .repository
+            .find_reference(&format!("refs/tags/{name}"))
+            .is_ok()
+        {
+            ceprintlns!("Tag"!Red, "{name} exists already.");
+            Err(ExitCode::DataErr)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Discover the repository enclosing the given path.
+    ///
+    /// Unless forced, the working tree must not contain any uncommitted
+    /// changes to tracked files such that the version bump can be recorded on
+    /// its own.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::DataErr`]
+    /// - [`sysexits::ExitCode::Unavailable`]
+    /// - [`sysexits::ExitCode::Usage`]
+    pub fn open(path: &Path, force: bool) -> Result<Self> {
+        let Ok(repository) = Repository::discover(path) else {
+            eprintln!("{} is not within a Git repository.", path.display());
+            return Err(ExitCode::Usage);
+        };
+        let dirty = !repository
+            .statuses(Some(
+                StatusOptions::new()
+                    .include_ignored(false)
+                    .include_untracked(false),
+            ))
+            .map_err(|e| unavailable(&e))?
+            .is_empty();
+
+        if dirty && !force {
+            ceprintlns!(
+                "Repository"!Red,
+                "has uncommitted changes, use `--force` to proceed anyway."
+            );
+            return Err(ExitCode::DataErr);
+        }
+
+        Ok(Self { repository })
+    }
+
+    /// Create an annotated tag for the given commit.
+    ///
+    /// # Errors
+    ///
+    /// - [`sysexits::ExitCode::Config`]
+    /// - [`sysexits::ExitCode::Unavailable`]
+    pub fn tag(&self, name: &str, target: Oid, message: &str) -> Result<()> {
+        let object = self
+            .repository
+            .find_object(target, None)
+            .map_err(|e| unavailable(&e))?;
+
+        self.repository
+            .tag(name, &object, &self.signature()?, message, false)
+            .map(|_| ())
+            .map_err(|e| unavailable(&e))
+    }
+
+    fn root(&self) -> Result<PathBuf> {
+        self.repository
+            .workdir()
+            .and_then(|root| root.canonicalize().ok())
+            .ok_or_else(|| {
+                eprintln!("The repository does not have a working tree.");
+                ExitCode::Usage
+            })
+    }
+
+    fn signature(&self) -> Result<Signature<'static>> {
+        self.repository.signature().map_err(|e| {
+            eprintln!("{e}");
+            ExitCode::Config
+        })
+    }
+}
+
+/// Report a failed Git operation.
+fn unavailable(error: &git2::Error) -> ExitCode {
+    eprintln!("{error}");
+    ExitCode::Unavailable
+}
+
+/******************************************************************************/
@@ -0,0 +1,314 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use std::{ops::Range, path::Path};
+
+/// The manifests whose own version field is edited in a structured way.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Manifest {
+    /// A Helm `Chart.yaml` with its top-level `version`.
+    ChartYaml,
+
+    /// A .NET project file with the version properties of its property groups.
+    Csproj,
+
+    /// An npm `package.json` with its top-level `version`.
+    PackageJson,
+
+    /// A Maven `pom.xml` with the `version` of its `project`.
+    PomXml,
+
+    /// A Python `pyproject.toml` with the `version` of its `[project]` or its
+    /// `[tool.poetry]` table.
+    PyprojectToml,
+
+    /// A Python `setup.cfg` with the `version` of its `[metadata]` section.
+    SetupCfg,
+}
+
+impl Manifest {
+    /// Select the manifest type of the given file by its name or extension.
+    pub fn detect(file: &Path) -> Option<Self> {
+        let name = file.file_name()?.to_str()?;
+
+        match name {
+            "Chart.yaml" => Some(Self::ChartYaml),
+            "package.json" => Some(Self::PackageJson),
+            "pom.xml" => Some(Self::PomXml),
+            "pyproject.toml" => Some(Self::PyprojectToml),
+            "setup.cfg" => Some(Self::SetupCfg),
+            _ => (file.extension()? == "csproj").then_some(Self::Csproj),
+        }
+    }
+
+    /// The name of the version field to report if it should be missing.
+    pub const fn field(self) -> &'static str {
+        match self {
+            Self::ChartYaml | Self::PackageJson => "version",
+            Self::Csproj => "PropertyGroup.Version",
+            Self::PomXml | Self::PyprojectToml => "project.version",
+            Self::SetupCfg => "metadata.version",
+        }
+    }
+
+    /// Find the values of the version fields within the given data.
+    pub fn locate(self, data: &str) -> Vec<Range<usize>> {
+        match self {
+            Self::ChartYaml => yaml_values(data, "version"),
+            Self::Csproj => xml_values(data, |path| {
+                matches!(
+                    path,
+                    [
+                        "Project",
+                        "PropertyGroup",
+                        "AssemblyVersion"
+                            | "FileVersion"
+                            | "PackageVersion"
+                            | "Version"
+                            | "VersionPrefix"
+                    ]
+                )
+            }),
+            Self::PackageJson => {
+                json_value(data, "version").into_iter().collect()
+            }
+            Self::PomXml => {
+                xml_values(data, |path| path == ["project", "version"])
+                    .into_iter()
+                    .take(1)
+                    .collect()
+            }
+            Self::PyprojectToml => {
+                toml_values(data, &["project", "tool.poetry"], "version")
+            }
+            Self::SetupCfg => ini_values(data, "metadata", "version"),
+        }
+    }
+}
+
+/// Find the quoted string value assigned to the given key within a line.
+pub fn value_of(line: &str, key: &str) -> Option<Range<usize>> {
+    for (index, _) in line.match_indices(key) {
+        if line[..index]
+            .ends_with(|c: char| c.is_alphanumeric() || "-._".contains(c))
+        {
+            continue;
+        }
+
+        let Some(value) = line[index + key.len()..]
+            .trim_start()
+            .strip_prefix('=')
+            .and_then(|rest| rest.trim_start().strip_prefix('"'))
+        else {
+            continue;
+        };
+        let start = line.len() - value.len();
+
+        return Some(start..start + value.find('"')?);
+    }
+
+    None
+}
+
+/// Find the values of the given key within the given section of an INI file.
+fn ini_values(data: &str, section: &str, key: &str) -> Vec<Range<usize>> {
+    let mut current = "";
+    let mut offset = 0;
+    let mut result = Vec::new();
+
+    for line in data.split_inclusive('\n') {
+        let trimmed = line.trim();
+
+        if let Some(name) =
+            trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']'))
+        {
+            current = name.trim();
+        } else if current == section {
+            if let Some((name, value)) = line.split_once(['=', ':']) {
+                let value = value.trim();
+
+                if name.trim() == key && !value.is_empty() {
+                    let start = offset + line.find(value).unwrap_or_default();
+
+                    result.push(start..start + value.len());
+                }
+            }
+        }
+
+        offset += line.len();
+    }
+
+    result
+}
+
+/// Find the string value of the given top-level key of a JSON object.
+fn json_value(data: &str, key: &str) -> Option<Range<usize>> {
+    let bytes = data.as_bytes();
+    let mut depth = 0_usize;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            b'"' => {
+                let end = string_end(bytes, index + 1)?;
+
+                if depth == 1 && &data[index + 1..end] == key {
+                    if let Some(value) = data[end + 1..]
+                        .trim_start()
+                        .strip_prefix(':')
+                        .and_then(|rest| rest.trim_start().strip_prefix('"'))
+                    {
+                        let start = data.len() - value.len();
+
+                        return Some(start..string_end(bytes, start)?);
+                    }
+                }
+
+                index = end;
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Find the index of the quote terminating the JSON string starting at the
+/// given index.
+fn string_end(bytes: &[u8], mut index: usize) -> Option<usize> {
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => return Some(index),
+            b'\\' => index += 2,
+            _ => index += 1,
+        }
+    }
+
+    None
+}
+
+/// Find the values of the given key within the given tables of a TOML file.
+fn toml_values(data: &str, tables: &[&str], key: &str) -> Vec<Range<usize>> {
+    let mut current = String::new();
+    let mut offset = 0;
+    let mut result = Vec::new();
+
+    for line in data.split_inclusive('\n') {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') {
+            current = trimmed
+                .trim_matches(['[', ']'])
+                .split('.')
+                .map(|key| key.trim().trim_matches(['"', '\'']))
+                .collect::<Vec<_>>()
+                .join(".");
+        } else if tables.contains(&current.as_str()) && trimmed.starts_with(key)
+        {
+            if let Some(range) = value_of(line, key) {
+                result.push(offset + range.start..offset + range.end);
+            }
+        }
+
+        offset += line.len();
+    }
+
+    result
+}
+
+/// Find the texts of the leaf elements of an XML document whose paths of tag
+/// names are accepted by the given predicate.
+fn xml_values(
+    data: &str,
+    accept: impl Fn(&[&str]) -> bool,
+) -> Vec<Range<usize>> {
+    let mut index = 0;
+    let mut path = Vec::new();
+    let mut result = Vec::new();
+    let mut text = None;
+
+    while let Some(start) = data[index..].find('<').map(|i| index + i) {
+        let terminator = if data[start..].starts_with("<!--") {
+            "-->"
+        } else if data[start..].starts_with("<![CDATA[") {
+            "]]>"
+        } else {
+            ">"
+        };
+        let Some(end) = data[start..]
+            .find(terminator)
+            .map(|i| start + i + terminator.len())
+        else {
+            break;
+        };
+        let tag = &data[start + 1..end - 1];
+
+        if tag.starts_with('/') {
+            if let Some(text) = text.take() {
+                if accept(&path) {
+                    result.push(text..start);
+                }
+            }
+
+            path.pop();
+        } else if !tag.starts_with(['!', '?']) && !tag.ends_with('/') {
+            path.push(tag.split_whitespace().next().unwrap_or_default());
+            text = Some(end);
+        }
+
+        index = end;
+    }
+
+    result
+}
+
+/// Find the values of the given top-level key of a YAML file.
+fn yaml_values(data: &str, key: &str) -> Vec<Range<usize>> {
+    let mut offset = 0;
+    let mut result = Vec::new();
+
+    for line in data.split_inclusive('\n') {
+        if let Some(value) = line
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            let value = value.split(" #").next().unwrap_or_default().trim();
+            let value = value.trim_matches(['"', '\'']);
+
+            if !value.is_empty() {
+                let start = offset
+                    + key.len()
+                    + 1
+                    + line[key.len() + 1..].find(value).unwrap_or_default();
+
+                result.push(start..start + value.len());
+            }
+        }
+
+        offset += line.len();
+    }
+
+    result
+}
+
+/******************************************************************************/
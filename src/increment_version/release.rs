@@ -0,0 +1,200 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use crate::{Version, VersionRange};
+use std::str::FromStr;
+use sysexits::ExitCode;
+
+/// The range to increment a [`Release`] by.
+///
+/// In addition to the ranges of [`VersionRange`], pre-releases can be created.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Range {
+    /// Create a Semantic Versioning major release.
+    Major,
+
+    /// Create a Semantic Versioning minor release.
+    Minor,
+
+    /// Create a Semantic Versioning patch release.
+    Patch,
+
+    /// Create a pre-release of the next major release.
+    Premajor,
+
+    /// Create a pre-release of the next minor release.
+    Preminor,
+
+    /// Create a pre-release of the next patch release.
+    Prepatch,
+
+    /// Create the next pre-release of the current pre-release or, if there is
+    /// none, a pre-release of the next patch release.
+    Prerelease,
+}
+
+crate::enum_trait!(Range {
+  Major <-> "major",
+  Minor <-> "minor",
+  Patch <-> "patch",
+  Premajor <-> "premajor",
+  Preminor <-> "preminor",
+  Prepatch <-> "prepatch",
+  Prerelease <-> "prerelease"
+});
+
+impl Range {
+    /// Convert a [`VersionRange`] into the corresponding release range.
+    #[must_use]
+    pub const fn from_version_range(range: VersionRange) -> Self {
+        match range {
+            VersionRange::Major => Self::Major,
+            VersionRange::Minor => Self::Minor,
+            VersionRange::Patch => Self::Patch,
+        }
+    }
+
+    /// The part of the [`Version`] to increment.
+    const fn part(self) -> VersionRange {
+        match self {
+            Self::Major | Self::Premajor => VersionRange::Major,
+            Self::Minor | Self::Preminor => VersionRange::Minor,
+            Self::Patch | Self::Prepatch | Self::Prerelease => {
+                VersionRange::Patch
+            }
+        }
+    }
+}
+
+/// A version with an optional pre-release identifier, such as `4.0.0-rc.1`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    pre: Option<String>,
+    version: Version,
+}
+
+impl Release {
+    /// Increment this release by the given range.
+    ///
+    /// Pre-releases are numbered by the given identifier, starting with 1.  A
+    /// release range applied to a pre-release of that very range will finalise
+    /// the pre-release, such that `4.0.0-rc.2` becomes `4.0.0` as major
+    /// release.
+    #[must_use]
+    pub fn increment(&self, range: Range, identifier: &str) -> Self {
+        let mut version = self.version;
+        let first = Some(format!("{identifier}.1"));
+        let pre = match range {
+            Range::Major | Range::Minor | Range::Patch => {
+                if !self.finalises(range) {
+                    version.increment(range.part());
+                }
+
+                None
+            }
+            Range::Premajor | Range::Preminor | Range::Prepatch => {
+                version.increment(range.part());
+                first
+            }
+            Range::Prerelease => {
+                if self.pre.is_none() {
+                    version.increment(range.part());
+                }
+
+                Some(
+                    self.pre
+                        .as_deref()
+                        .and_then(|pre| pre.strip_prefix(identifier))
+                        .and_then(|n| n.strip_prefix('.'))
+                        .and_then(|n| n.parse::<usize>().ok())
+                        .map_or_else(
+                            || format!("{identifier}.1"),
+                            |n| format!("{identifier}.{}", n + 1),
+                        ),
+                )
+            }
+        };
+
+        Self { pre, version }
+    }
+
+    /// Whether this version is a pre-release.
+    pub const fn is_pre_release(&self) -> bool {
+        self.pre.is_some()
+    }
+
+    /// The release part of this version.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    const fn finalises(&self, range: Range) -> bool {
+        self.pre.is_some()
+            && match range {
+                Range::Major => {
+                    self.version.minor() == 0 && self.version.patch() == 0
+                }
+                Range::Minor => self.version.patch() == 0,
+                _ => true,
+            }
+    }
+}
+
+impl std::fmt::Display for Release {
+    /// The string representation without any `v` prefix, as used by manifests.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.version.major(),
+            self.version.minor(),
+            self.version.patch()
+        )?;
+
+        if let Some(pre) = &self.pre {
+            write!(f, "-{pre}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Release {
+    type Err = ExitCode;
+
+    /// Split the pre-release identifier off and parse the remainder as
+    /// [`Version`].
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let (version, pre) = string
+            .split_once('-')
+            .map_or((string, None), |(v, p)| (v, Some(p.to_string())));
+
+        if pre.as_deref() == Some("") {
+            eprintln!("This version is invalid.");
+            return Err(ExitCode::DataErr);
+        }
+
+        Ok(Self {
+            pre,
+            version: Version::from_str(version)?,
+        })
+    }
+}
+
+/******************************************************************************/
@@ -73,6 +73,7 @@
 //!    1. [`cffreference`](#cffreference)
 //!    1. ⚠️  [`cff-release-today`](#cff-release-today) (deprecated)
 //!    1. [`comment-changes`](#comment-changes)
+//!    1. [`commit-msg`](#commit-msg)
 //!    1. [`complain`](#complain)
 //!    1. [`increment-version`](#increment-version)
 //!    1. [`mkcws`](#mkcws)
@@ -84,6 +85,7 @@
 //!    1. [`cffreference`](#cffreference)
 //!    1. ⚠️  [`cff-release-today`](#cff-release-today) (deprecated)
 //!    1. [`comment-changes`](#comment-changes)
+//!    1. [`commit-msg`](#commit-msg)
 //!    1. [`complain`](#complain)
 //!    1. [`graph-description`](#graph-description)
 //!    1. [`increment-version`](#increment-version)
@@ -154,6 +156,14 @@
 //!   [![](https://img.shields.io/crates/l/git2)
 //!   ](https://github.com/rust-lang/git2-rs)
 //!
+//! - [`globset`]
+//!   [![](https://img.shields.io/crates/l/globset)
+//!   ](https://github.com/BurntSushi/ripgrep)
+//!
+//! - [`ignore`]
+//!   [![](https://img.shields.io/crates/l/ignore)
+//!   ](https://github.com/BurntSushi/ripgrep)
+//!
 //! - [`indexmap`]
 //!   [![](https://img.shields.io/crates/l/indexmap)
 //!   ](https://github.com/bluss/indexmap)
@@ -162,6 +172,14 @@
 //!   [![](https://img.shields.io/crates/l/quick-xml)
 //!   ](https://github.com/tafia/quick-xml)
 //!
+//! - [`rayon`]
+//!   [![](https://img.shields.io/crates/l/rayon)
+//!   ](https://github.com/rayon-rs/rayon)
+//!
+//! - [`regex`]
+//!   [![](https://img.shields.io/crates/l/regex)
+//!   ](https://github.com/rust-lang/regex)
+//!
 //! - [`ron`]
 //!   [![](https://img.shields.io/crates/l/ron)
 //!   ](https://github.com/ron-rs/ron)
@@ -170,6 +188,14 @@
 //!   [![](https://img.shields.io/crates/l/serde)
 //!   ](https://github.com/serde-rs/serde)
 //!
+//! - [`serde_json`]
+//!   [![](https://img.shields.io/crates/l/serde_json)
+//!   ](https://github.com/serde-rs/json)
+//!
+//! - [`similar`]
+//!   [![](https://img.shields.io/crates/l/similar)
+//!   ](https://github.com/mitsuhiko/similar)
+//!
 //! - [`sysexits`]
 //!   [![](https://img.shields.io/crates/l/sysexits)
 //!   ](https://github.com/sorairolake/sysexits-rs)
@@ -178,6 +204,10 @@
 //!   [![](https://img.shields.io/crates/l/toml)
 //!   ](https://github.com/toml-rs/toml)
 //!
+//! - [`unicode_width`]
+//!   [![](https://img.shields.io/crates/l/unicode-width)
+//!   ](https://github.com/unicode-rs/unicode-width)
+//!
 //! ## Introduction
 //!
 //! `aeruginous` is a Rust application providing several development utilities.
@@ -301,6 +331,11 @@
 //! Markdown (MD), or the Rusty Object Notation (RON).  At option, hyperlinks
 //! can be specified.
 //!
+//! By default, the repository enclosing the current working directory will be
+//! analysed.  Another one can be chosen with `--repository`, pointing to any
+//! path within that repository.  Linked worktrees and bare repositories are
+//! supported, as well.
+//!
 //! As an example, a repository might contain these four commits:
 //!
 //! 1. ```Added ::= source file `a.rs`_```
@@ -345,6 +380,28 @@
 //!
 //! ```
 //!
+//! ### `commit-msg`
+//!
+//! > To be called with:
+//! >
+//! > - `commitmsg`
+//! > - `commit-msg`
+//! > - `lint-commit-msg`
+//!
+//! Commits not following the conventions configured for `comment-changes` will
+//! be skipped silently when harvesting the changes.  This mode accepts the same
+//! settings regarding the delimiter and the categories and reports each commit
+//! message which would not be harvested together with the reason therefore.
+//!
+//! The commit message can be read from a file, such that this mode is suitable
+//! for Git `commit-msg` hooks.  Comment lines will be ignored.  Alternatively,
+//! a revision range, such as `main..HEAD`, can be checked.
+//!
+//! ```bash
+//! aeruginous commit-msg -d ::= -k -i .git/COMMIT_EDITMSG
+//! aeruginous commit-msg -d ::= -k main..HEAD
+//! ```
+//!
 //! ### `complain`
 //!
 //! > To be called with:
@@ -356,15 +413,28 @@
 //!
 //! 1. Every file needs to be terminated by a line feed.
 //! 1. Files must not contain CRLFs.
-//! 1. Lines shall have a width of at most n characters.
+//! 1. Lines shall have a width of at most n columns.
 //! 1. Trailing white space characters must be removed.
 //! 1. Lines have to be indented by spaces / tabs.
 //! 1. Spaces and tabs must not be mixed for indentation.
 //! 1. Within any line, there shall not be any tab character.
-//!
-//! All rules can be ignored, the line width as well as the indentation unit can
-//! be configured.  Every violation is reported to [`std::io::Stderr`] with the
-//! number of the rule being highlighted using the following colours.
+//! 1. Files must not start with a UTF-8 BOM.
+//! 1. Files must not end with blank lines.
+//! 1. Files must not mix CRLFs and LFs as line endings.
+//! 1. There shall not be more than n consecutive blank lines.
+//! 1. Merge conflict markers must be removed.
+//! 1. Invisible and bidirectional control characters must not be used.
+//! 1. Files have to start with the configured license header.
+//! 1. Markdown headings need to be surrounded by blank lines.
+//! 1. YAML files must not be indented by tabs.
+//!
+//! All rules can be ignored, the line width, the maximum count of consecutive
+//! blank lines, as well as the indentation unit can be configured.  The width
+//! of a line is measured like in a terminal:  tabs are expanded to the tab stop
+//! width set by `--tab-width`, East Asian wide characters take two columns, and
+//! zero-width characters none.  Every violation is reported to
+//! [`std::io::Stderr`] with the number of the rule being highlighted using the
+//! following colours.
 //!
 //! | Colour | Meaning                       |
 //! |:------:|:------------------------------|
@@ -374,7 +444,105 @@
 //!
 //! After all rules have been checked for one file, a summary will be written to
 //! [`std::io::Stderr`] consisting of an ASCII art crab as this application is
-//! written in Rust, the number of violations, as well as the file name.
+//! written in Rust, the number of violations, as well as the file name.  With
+//! `--format rich`, each violation is shown like a diagnostic of `rustc` with
+//! its position, the affected source line, and carets marking the offending
+//! characters.  Thereby, trailing white space is made visible.
+//!
+//! For the integration with further tools, the violations can also be reported
+//! in one of the following machine-readable formats by setting `--format`.  The
+//! report will be written to [`std::io::Stdout`] or the file given by
+//! `--output`.
+//!
+//! | Format       | Description                                    |
+//! |:-------------|:-----------------------------------------------|
+//! | `checkstyle` | Checkstyle XML                                 |
+//! | `github`     | GitHub Actions workflow commands (annotations) |
+//! | `json`       | a JSON array of all violations                 |
+//! | `sarif`      | SARIF 2.1.0                                    |
+//!
+//! Most violations can be fixed automatically with `--fix`.  This will add the
+//! missing final line feed, convert CRLFs, remove trailing white space, apply
+//! the configured indentation unit, expand tabs within lines, using the tab
+//! stop width set by `--tab-width`, and insert missing license headers.
+//! Violations which cannot be fixed, such as too long lines, will be reported
+//! afterwards.  Lines within mercy regions will not be touched.  To preview the
+//! changes as unified diff on [`std::io::Stdout`], use `--diff`; a report in
+//! any other format than the human-readable one then has to be written to a
+//! file by `--output`.
+//!
+//! Single violations can be tolerated by mercy markers naming the rule's number
+//! `NNNN`.  `#[aeruginous::mercy::NNNN]` suppresses the rule in the line
+//! containing the marker, `#[aeruginous::mercy::NNNN::next]` in the following
+//! line, and `#[aeruginous::mercy::NNNN::file]` in the whole file.  Regions are
+//! enclosed by `#[aeruginous::mercy::NNNN::start]` and the corresponding
+//! `#[aeruginous::mercy::NNNN::end]`.  With `--report-unused-mercy`, markers
+//! which do not suppress any violation will be reported as ÆC-0000.
+//!
+//! In order to adopt this linter in an existing project, the current violations
+//! can be recorded as baseline by `--write-baseline`.  When checking the files
+//! with `--baseline`, only the violations which are not listed in the baseline
+//! will be reported.  Since the baseline identifies violations by the content
+//! of the affected lines, moving code around will not invalidate it.  Fixed
+//! violations can be removed from the baseline with `--prune-baseline`; the
+//! entries of files which were not checked in that run are kept.
+//!
+//! To only judge the lines touched in a pull request, set `--diff-base` to the
+//! revision to compare the working tree of the enclosing Git repository with.
+//! Then, only violations in changed lines will be reported, as well as those of
+//! file-level rules, such as ÆC-0001, in changed files.
+//!
+//! The settings can be adjusted for certain paths by a TOML file given with
+//! `--config`.  Each `[[section]]` lists the glob patterns of the `files` it
+//! applies to as well as the settings to override, named like the command line
+//! options, such as `line_width = 100` or `indent_by = "tabs"`.  Later sections
+//! take precedence over earlier ones.  Furthermore, the `.editorconfig` files
+//! enclosing the checked files are respected, unless `--ignore-editorconfig` is
+//! set.  They can adjust the rules by the properties `end_of_line`,
+//! `indent_size`, `indent_style`, `insert_final_newline`, `max_line_length`,
+//! `tab_width`, and `trim_trailing_whitespace` and will be overridden by the
+//! configuration file, which is in turn overridden by the command line options
+//! given explicitly.
+//!
+//! Project-specific rules can be declared in the configuration file, too.  Each
+//! `[[rule]]` has an `id`, a `regex` to search for in every line, a `message`,
+//! and optionally a `severity`, a `colour` (`green`, `yellow`, or `red`), and
+//! the `files` it applies to.  These rules are checked alongside the built-in
+//! ones and can be suppressed by mercy markers naming their `id`.
+//!
+//! A license header is declared by a `[header]` with a `template` containing
+//! the placeholders `{year}` and `{holder}`, the `holder`, and optionally the
+//! `files` it applies to.  The comment style is chosen by the file extension:
+//! common languages default to line comments, such as `// ` or `# `, and
+//! `[header.styles.<extension>]` can set a `first` and `last` line, a `prefix`
+//! and `suffix` for each line, and a `width` to pad the lines to, for instance
+//! to draw a box.  With `current_year = true`, headers whose year range does
+//! not reach the current year are reported and updated by `--fix`, too.
+//!
+//! Some rules are adjusted for certain languages by built-in profiles, selected
+//! by the file name or extension, unless `--ignore-profiles` is set.  In
+//! Markdown files, two trailing spaces are accepted as hard line break, link
+//! definitions may exceed the line width, and headings are checked for their
+//! spacing.  Makefiles have to be indented by tabs, and YAML files must not
+//! contain any tabs in their indentation.  The profiles are applied first such
+//! that the `.editorconfig` files, the configuration file, and the explicit
+//! command line options, in this order, can still override them.
+//!
+//! Directories are searched recursively.  Thereby, the `.git` directory as well
+//! as all paths ignored by `.gitignore`, `.ignore`, and `.complainignore` files
+//! are skipped, just like those matching any of the globs given by `--exclude`.
+//! Binary files and files not encoded in UTF-8 are skipped with a notice.  The
+//! files are checked in parallel, each in a single pass over its lines, while
+//! the report always lists them in the order they were found in.
+//!
+//! Unsaved editor buffers and staged blobs can be checked by passing `-` as
+//! file such that the contents are read from [`std::io::Stdin`].  The path
+//! given by `--stdin-filename` is then reported and used to select the profile,
+//! the `.editorconfig` settings, and the sections of the configuration file.
+//! With `--fix`, the fixed contents are written to [`std::io::Stdout`] instead
+//! of being stored.  The human-readable report is written to
+//! [`std::io::Stderr`] anyway, while a report in any other format has to be
+//! written to a file by `--output` in this mode.
 //!
 //! <!--
 //! ### `graph-description`
@@ -405,6 +573,91 @@
 //! This subcommand will increment the hard-coded version strings in the given
 //! files by the specified version range.
 //!
+//! Besides the ranges `major`, `minor`, and `patch`, there are the pre-release
+//! ranges `premajor`, `preminor`, and `prepatch` which create the first
+//! pre-release of the respective next release, such as `4.0.0-rc.1`, as well as
+//! `prerelease` which steps to the next pre-release, such as from `4.0.0-rc.1`
+//! to `4.0.0-rc.2`.  The identifier of the pre-releases defaults to `rc` and
+//! can be changed by `--preid`.  A release range applied to a pre-release of
+//! that range finalises it, such that `4.0.0-rc.2` becomes `4.0.0` as `major`
+//! release.  Alternatively, `--set` jumps to an explicit version.  Requirements
+//! of path dependencies are always set to the entire version if it should be a
+//! pre-release since Cargo would not match pre-releases, otherwise.
+//!
+//! Cargo workspaces are supported as well.  In `Cargo.toml` files, the version
+//! in both the `[package]` and the `[workspace.package]` section is updated,
+//! while members inheriting it by `version.workspace = true` are left as they
+//! are.  The version requirements of path dependencies are adjusted, too, if
+//! they should match the old version, keeping their operator and precision,
+//! such that `version = "1.2"` becomes `version = "1.3"` for a minor release.
+//! In `Cargo.lock` files, all local packages of the old version are updated.
+//! If `--package` should be given, only the path dependencies on and the lock
+//! file entries of that package are considered.
+//!
+//! Further manifests are recognised by their names such that only the version
+//! field of the project itself is edited, instead of any occurrence of the old
+//! version:
+//!
+//! | Manifest         | Version Field                                       |
+//! |:-----------------|:----------------------------------------------------|
+//! | `*.csproj`       | `Version`, `VersionPrefix`, and the like            |
+//! | `Chart.yaml`     | top-level `version`                                 |
+//! | `package.json`   | top-level `version`                                 |
+//! | `pom.xml`        | `version` of the `project`                          |
+//! | `pyproject.toml` | `version` in `[project]` or `[tool.poetry]`         |
+//! | `setup.cfg`      | `version` in `[metadata]`                           |
+//!
+//! The application aborts if the version field of such a manifest should be
+//! missing.
+//!
+//! Any other location of the version, such as a constant in the source code or
+//! a label in a Dockerfile, can be listed in a configuration file set by
+//! `--config`.  Each `[[file]]` entry names a `path`, relative to the
+//! configuration file, a `search` template, and an optional `replace` template
+//! which defaults to the search template.  The placeholders `{major}`,
+//! `{minor}`, `{patch}`, and `{version}`, including any pre-release identifier,
+//! are filled with the old version for searching and with the new version for
+//! replacing.  The application aborts if any search template should not be
+//! found.  Since the search templates are applied after the files given by
+//! `--edit` and `--rewrite` were edited, the files listed there should not be
+//! passed to these options at the same time.
+//!
+//! ```toml
+//! [[file]]
+//! path = "src/lib.rs"
+//! search = 'pub const VERSION: &str = "v{version}";'
+//!
+//! [[file]]
+//! path = "Dockerfile"
+//! search = "LABEL version={version}"
+//! ```
+//!
+//! If `--old-version` should be omitted, the current version is detected from
+//! the `package.version` field of the given `Cargo.toml` files, respecting
+//! `--package`, from the `version:` line of the given `CITATION.cff` files, and
+//! from the newest section of the RONLOG set by `--changelog`, defaulting to
+//! `CHANGELOG.ron`, if existing.  In case these sources should disagree, the
+//! application aborts with a report on the versions found.
+//!
+//! All edits are computed in memory first such that no file is written if any
+//! of them should fail.  With `--dry-run`, the edits, including the updated
+//! `Cargo.lock` entries and release dates of `CITATION.cff` files, are only
+//! printed as unified diff per file to [`std::io::Stdout`] instead of being
+//! written.  `--check` does the same but fails if any file would change, for
+//! instance, to ensure in CI that a version bump is not pending.
+//!
+//! Afterwards, `--commit` records the changed files in a new commit of the Git
+//! repository enclosing the first given file.  The commit message is set by
+//! `--message`, defaulting to `Bump version to v{version}`, and supports the
+//! same placeholders as the configuration file.  Since the bump should not be
+//! mixed up with unrelated work, the application refuses to run on a working
+//! tree with uncommitted changes unless `--force` is given; these changes are
+//! then left out of the commit, no matter whether they are staged.  `--tag`
+//! additionally creates an annotated tag named after the new version.  Its
+//! message consists of the introduction and changes of the RONLOG section
+//! documenting the new version, if existing, and of the commit message,
+//! otherwise.
+//!
 //! ### `mkcws`
 //!
 //! > To be called with:
@@ -527,15 +780,18 @@ pub use crate::{
     application::{Action, Clap as Application},
     cff::Cffreference,
     changelog::{
-        CommentChanges, Fragment, FragmentExportFormat, Ronlog, RonlogAction,
-        RonlogReferences, RonlogSection,
+        CommentChanges, CommitMsg, Fragment, FragmentExportFormat, Ronlog,
+        RonlogAction, RonlogReferences, RonlogSection,
+    },
+    complain::{
+        Complain, Diagnostic as ComplainDiagnostic, IndentationUnit,
+        OutputFormat as ComplainOutputFormat, Severity as ComplainSeverity,
     },
-    complain::{Complain, IndentationUnit},
     graphing::{
         AeruginousGraphDescription, AgdTokens, EdgeType, Edges, Graph,
         VertexData, Vertices,
     },
-    increment_version::IncrementVersion,
+    increment_version::{IncrementVersion, Range as IncrementVersionRange},
     pattern::{
         Buffer as PatternBuffer, IOProcessor as PatternIOProcessor,
         Writer as PatternWriter,
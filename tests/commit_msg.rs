@@ -0,0 +1,92 @@
+/*********************** GNU General Public License 3.0 ***********************\
+|                                                                              |
+|  Copyright (C) 2023 Kevin Matthes                                            |
+|                                                                              |
+|  This program is free software: you can redistribute it and/or modify        |
+|  it under the terms of the GNU General Public License as published by        |
+|  the Free Software Foundation, either version 3 of the License, or           |
+|  (at your option) any later version.                                         |
+|                                                                              |
+|  This program is distributed in the hope that it will be useful,             |
+|  but WITHOUT ANY WARRANTY; without even the implied warranty of              |
+|  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the               |
+|  GNU General Public License for more details.                                |
+|                                                                              |
+|  You should have received a copy of the GNU General Public License           |
+|  along with this program.  If not, see <https://www.gnu.org/licenses/>.      |
+|                                                                              |
+\******************************************************************************/
+
+use aeruginous::CommitMsg;
+use clap::Parser;
+use std::{
+    fs::{remove_file, write},
+    path::PathBuf,
+};
+
+macro_rules! make_test {
+    ( @content $( $name:ident : $text:literal -> $n:tt ),+ ) => {
+        $(
+            #[test]
+            fn $name() {
+                let file = concat!(stringify!($name), ".txt");
+                let cm = CommitMsg::new(
+                    "::=".to_string(),
+                    Some(PathBuf::from(file)),
+                    None,
+                );
+
+                write(file, $text).unwrap();
+
+                assert_eq!(cm.main().is_ok(), $n == 0);
+                assert_eq!($n, cm.process().unwrap());
+
+                remove_file(file).unwrap();
+            }
+        )+
+    };
+
+    ( @keep_a_changelog $( $name:ident : $text:literal -> $n:tt ),+ ) => {
+        $(
+            #[test]
+            fn $name() {
+                let file = concat!(stringify!($name), ".txt");
+                let cm = CommitMsg::parse_from([
+                    "commit-msg",
+                    "-d",
+                    "::=",
+                    "-k",
+                    "-i",
+                    file,
+                ]);
+
+                write(file, $text).unwrap();
+
+                assert_eq!(cm.main().is_ok(), $n == 0);
+                assert_eq!($n, cm.process().unwrap());
+
+                remove_file(file).unwrap();
+            }
+        )+
+    };
+}
+
+make_test!(@content
+    comments_only: "# Please enter the commit message.\n" -> 1,
+    empty_message: "" -> 1,
+    leading_comments: "# comment\n\nAdded ::= feature\n" -> 0,
+    missing_delimiter: "Update something\n" -> 1,
+    scissors: "\
+Fixed ::= bug
+# ------------------------ >8 ------------------------
+Added ::= hidden
+" -> 0,
+    valid_message: "Added ::= feature\n\nSome body.\n" -> 0
+);
+
+make_test!(@keep_a_changelog
+    keep_a_changelog_invalid: "Misc ::= something\n" -> 1,
+    keep_a_changelog_valid: "Security ::= fix exploit\n" -> 0
+);
+
+/******************************************************************************/
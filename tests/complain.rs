@@ -97,7 +97,16 @@ mod complain {
         ac_0005_1: "\tabc\n" -> 1,
         ac_0006_1: " \t\n" -> 1,
         ac_0006_2: " \tabc\n" -> 1,
-        ac_0007_1: "abc\tabc\n" -> 1
+        ac_0007_1: "abc\tabc\n" -> 1,
+        ac_0008_1: "\u{feff}abc\n" -> 1,
+        ac_0009_1: "abc\n\n" -> 1,
+        ac_0009_2: "abc\n\n \n" -> 2,
+        ac_0010_1: "abc\nabc\r\n" -> 2,
+        ac_0011_1: "abc\n\n\n\nabc\n" -> 1,
+        ac_0012_1: "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> main\n" -> 3,
+        ac_0013_1: "abc\u{200b}\n" -> 1,
+        ac_0013_2: "/* \u{202e} } \u{2066} */\n" -> 2,
+        ac_0013_3: "\u{202e}abc\n" -> 1
     );
 
     make_test!(@content @tabs
@@ -157,6 +166,49 @@ mod complain {
         );
     }
 
+    #[test]
+    fn ignore_new_lints() {
+        let file = "ignore_new_lints.txt";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        write(
+            file,
+            "\u{feff}<<<<<<< HEAD\n\n\n\n\u{200b}\r\n=======\n>>>>>>>\n\n",
+        )
+        .unwrap();
+
+        assert_eq!(ac.process().unwrap(), 9);
+
+        ac.ignore_byte_order_mark();
+        ac.ignore_carriage_return_line_feeds();
+        ac.ignore_consecutive_blank_lines();
+        ac.ignore_invisible_characters();
+        ac.ignore_merge_conflict_markers();
+        ac.ignore_mixed_line_endings();
+        ac.ignore_trailing_blank_lines();
+
+        assert!(ac.main().is_ok());
+
+        remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn max_blank_lines() {
+        let file = "max_blank_lines.txt";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        write(file, "Title\n=======\n\n\n\nabc\n").unwrap();
+        ac.max_blank_lines(3);
+
+        assert!(ac.main().is_ok());
+
+        ac.max_blank_lines(2);
+
+        assert_eq!(ac.diagnose().unwrap()[0].line(), 5);
+
+        remove_file(file).unwrap();
+    }
+
     #[test]
     fn repository_check() {
         let mut ac = Complain::default();
@@ -168,6 +220,1017 @@ mod complain {
     }
 }
 
+mod traversal {
+    use aeruginous::Complain;
+    use std::{
+        fs::{create_dir_all, remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn skipped_paths() {
+        let dir = "skipped_paths";
+        let mut ac = Complain::new(vec![PathBuf::from(dir)]);
+
+        for sub in ["build", "excluded", "generated", "src"] {
+            create_dir_all(format!("{dir}/{sub}")).unwrap();
+            write(format!("{dir}/{sub}/tws.txt"), "abc \n").unwrap();
+        }
+
+        write(format!("{dir}/.gitignore"), "build/\n").unwrap();
+        write(format!("{dir}/.complainignore"), "generated/\n").unwrap();
+        write(format!("{dir}/src/binary.dat"), b"abc \0\x01").unwrap();
+        write(format!("{dir}/src/latin1.txt"), b"\xe4 \n").unwrap();
+        ac.exclude("excluded");
+
+        assert_eq!(ac.process().unwrap(), 1);
+        assert_eq!(
+            ac.diagnose().unwrap()[0].file(),
+            &PathBuf::from(format!("{dir}/src/tws.txt"))
+        );
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn stable_order() {
+        let dir = "stable_order";
+        let ac = Complain::new(vec![PathBuf::from(dir)]);
+
+        create_dir_all(dir).unwrap();
+
+        for i in 0..300 {
+            write(format!("{dir}/{i:03}.txt"), "abc \ndef").unwrap();
+        }
+
+        let files = ac
+            .diagnose()
+            .unwrap()
+            .iter()
+            .map(|d| (d.file().clone(), d.rule().clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(files.len(), 600);
+        assert!(files.windows(2).all(|w| w[0] < w[1]));
+
+        remove_dir_all(dir).unwrap();
+    }
+}
+
+mod diff_base {
+    use aeruginous::Complain;
+    use std::{
+        fs::{remove_file, write},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn changed_lines_only() {
+        let file = "changed_lines_only.txt";
+        let mut ac = Complain::new(vec![
+            PathBuf::from(file),
+            PathBuf::from("graphs/invalid/too_long_comments.agd"),
+        ]);
+
+        ac.diff_base("HEAD");
+        write(file, "abc \ndef").unwrap();
+
+        assert_eq!(
+            ac.diagnose()
+                .unwrap()
+                .iter()
+                .map(|d| (d.file().to_str().unwrap(), d.rule().as_str()))
+                .collect::<Vec<_>>(),
+            vec![(file, "ÆC-0001"), (file, "ÆC-0004")]
+        );
+
+        remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn invalid_revision() {
+        let mut ac = Complain::new(vec![PathBuf::from("LICENSE")]);
+
+        ac.diff_base("no-such-revision");
+
+        assert!(ac.main().is_err());
+    }
+}
+
+mod diagnostic {
+    use aeruginous::{Complain, ComplainSeverity};
+    use std::{
+        fs::{remove_file, write},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn diagnose() {
+        let file = "diagnose.txt";
+        let ac = Complain::new(vec![PathBuf::from(file)]);
+
+        write(file, "abc \n\tabc\tabc\n").unwrap();
+
+        let diagnostics = ac.diagnose().unwrap();
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.rule().as_str(), d.line(), d.column()))
+                .collect::<Vec<_>>(),
+            vec![("ÆC-0004", 1, 4), ("ÆC-0005", 2, 1), ("ÆC-0007", 2, 5)]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.severity() == ComplainSeverity::Error
+                && d.file() == &PathBuf::from(file)));
+
+        remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn diagnose_file() {
+        let file = "diagnose_file.txt";
+
+        write(file, "abc").unwrap();
+
+        let diagnostics = Complain::default().diagnose_file(file).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "diagnose_file.txt:1:4: ÆC-0001 File not terminated by line feed."
+        );
+
+        remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn diagnose_str() {
+        let file = "virtual/diagnose_str.md";
+        let data = "text  \n# Heading\ntext\n";
+        let diagnostics = Complain::default().diagnose_str(file, data).unwrap();
+
+        assert!(!PathBuf::from(file).exists());
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.rule().as_str(), d.line()))
+                .collect::<Vec<_>>(),
+            vec![("ÆC-0015", 2), ("ÆC-0015", 2)]
+        );
+        assert!(diagnostics.iter().all(|d| d.file() == &PathBuf::from(file)));
+    }
+
+    #[test]
+    fn severity_traits() {
+        assert_eq!(ComplainSeverity::default(), ComplainSeverity::Error);
+        assert_eq!(ComplainSeverity::Warning.to_string(), "warning");
+        assert_eq!("note".parse(), Ok(ComplainSeverity::Note));
+    }
+}
+
+mod line_width {
+    use aeruginous::Complain;
+    use std::{
+        fs::{remove_file, write},
+        path::PathBuf,
+    };
+
+    macro_rules! make_test {
+        ( $( $name:ident : $text:expr , $tab:literal -> $column:expr ),+ ) => {
+            $(
+                #[test]
+                fn $name() {
+                    let file = concat!(stringify!($name), ".txt");
+                    let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+                    ac.ignore_tabs_within_lines();
+                    ac.ignore_wrong_indentation();
+                    ac.tab_width($tab);
+                    write(file, $text + "\n").unwrap();
+
+                    assert_eq!(
+                        ac.diagnose()
+                            .unwrap()
+                            .iter()
+                            .map(|d| d.column())
+                            .collect::<Vec<_>>(),
+                        Vec::<usize>::from($column)
+                    );
+
+                    remove_file(file).unwrap();
+                }
+            )+
+        };
+    }
+
+    make_test!(
+        ascii: "a".repeat(81), 4 -> [81],
+        tabs_2: "\t".repeat(39) + "x", 2 -> [],
+        tabs_4: "\t".repeat(20) + "x", 4 -> [21],
+        tab_stop: "a".repeat(75) + "\tb", 8 -> [77],
+        wide: "\u{4e2d}".repeat(41), 4 -> [41],
+        zero_width: "e\u{301}".repeat(80), 4 -> []
+    );
+}
+
+mod mercy {
+    use aeruginous::{Complain, ComplainSeverity};
+    use std::{
+        fs::{remove_file, write},
+        path::PathBuf,
+    };
+
+    macro_rules! make_test {
+        ( $( $name:ident : $text:literal -> $( $rule:literal ),* );+ ) => {
+            $(
+                #[test]
+                fn $name() {
+                    let file = concat!(stringify!($name), ".txt");
+                    let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+                    ac.report_unused_mercy();
+                    write(file, $text).unwrap();
+
+                    assert_eq!(
+                        ac.diagnose()
+                            .unwrap()
+                            .iter()
+                            .map(|d| d.rule().as_str())
+                            .collect::<Vec<_>>(),
+                        Vec::<&str>::from([$( $rule ),*])
+                    );
+
+                    remove_file(file).unwrap();
+                }
+            )+
+        };
+    }
+
+    make_test!(
+        mercy_file:
+            "#[aeruginous::mercy::0004::file]\na \nb \n" -> ;
+        mercy_line:
+            "\ta #[aeruginous::mercy::0005]\n\tb\n" -> "ÆC-0005";
+        mercy_next:
+            "#[aeruginous::mercy::0007::next]\na\tb\na\tb\n" -> "ÆC-0007";
+        mercy_region:
+            "#[aeruginous::mercy::0002::start]\r\na\r\n\
+             #[aeruginous::mercy::0002::end]\r\na\r\n" -> "ÆC-0002";
+        mercy_unused:
+            "#[aeruginous::mercy::0004]\n" -> "ÆC-0000"
+    );
+
+    #[test]
+    fn unused_mercy_is_a_warning() {
+        let file = "unused_mercy_is_a_warning.txt";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        write(file, "#[aeruginous::mercy::0001::file]\n").unwrap();
+
+        assert!(ac.diagnose().unwrap().is_empty());
+
+        ac.report_unused_mercy();
+
+        let diagnostics = ac.diagnose().unwrap();
+
+        assert_eq!(diagnostics[0].severity(), ComplainSeverity::Warning);
+        assert_eq!(
+            diagnostics[0].message(),
+            "Mercy for 0001 without any effect."
+        );
+
+        remove_file(file).unwrap();
+    }
+}
+
+mod output_format {
+    use aeruginous::{Complain, ComplainOutputFormat};
+    use aeruginous_io::PathBufLikeReader;
+    use std::{
+        fs::{remove_file, write},
+        path::PathBuf,
+    };
+
+    macro_rules! make_test {
+        ( $( $name:ident : $format:ident -> $( $expected:literal ),+ );+ ) => {
+            $(
+                #[test]
+                fn $name() {
+                    let file = concat!(stringify!($name), ".txt");
+                    let report = concat!(stringify!($name), ".report");
+                    let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+                    ac.format(ComplainOutputFormat::$format);
+                    ac.output_file(report);
+                    write(file, "abc \n\tabc").unwrap();
+
+                    assert!(ac.main().is_err());
+
+                    let report_data = report.read_silently().unwrap();
+
+                    $(
+                        assert!(report_data.contains($expected));
+                    )+
+
+                    remove_file(file).unwrap();
+                    remove_file(report).unwrap();
+                }
+            )+
+        };
+    }
+
+    make_test!(
+        checkstyle: Checkstyle ->
+            "<checkstyle version=\"4.3\">",
+            "<file name=\"checkstyle.txt\">",
+            "<error line=\"1\" column=\"4\" severity=\"error\" \
+             message=\"TWS in line 1.\" source=\"ÆC-0004\"/>";
+        github: Github ->
+            "::error file=github.txt,line=2,col=5,title=ÆC-0001::File not \
+             terminated by line feed.\n",
+            "::error file=github.txt,line=2,col=1,title=ÆC-0005::Line 2 \
+             indented by tabs.\n";
+        json: Json ->
+            "\"file\": \"json.txt\"",
+            "\"rule\": \"ÆC-0004\"",
+            "\"severity\": \"error\"";
+        sarif: Sarif ->
+            "\"version\": \"2.1.0\"",
+            "\"ruleId\": \"ÆC-0005\"",
+            "\"startLine\": 2"
+    );
+
+    #[test]
+    fn debug_trait() {
+        assert_eq!(format!("{:?}", ComplainOutputFormat::Sarif), "Sarif");
+    }
+
+    #[test]
+    fn diff_requires_output_file() {
+        let file = "diff_requires_output_file.txt";
+        let report = "diff_requires_output_file.report";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        ac.diff();
+        ac.format(ComplainOutputFormat::Json);
+        write(file, "abc \n").unwrap();
+
+        assert_eq!(ac.process(), Err(sysexits::ExitCode::Usage));
+
+        ac.output_file(report);
+
+        assert_eq!(ac.process(), Ok(1));
+        assert!(report.read_silently().unwrap().contains("ÆC-0004"));
+
+        remove_file(file).unwrap();
+        remove_file(report).unwrap();
+    }
+
+    #[test]
+    fn rich() {
+        let file = "rich.txt";
+        let data = "abc \n\tabc";
+        let ac = Complain::new(vec![PathBuf::from(file)]);
+        let mut report = Vec::new();
+
+        write(file, data).unwrap();
+
+        let diagnostics = ac.diagnose().unwrap();
+
+        assert_eq!(
+            diagnostics.iter().map(|d| d.length()).collect::<Vec<_>>(),
+            [1, 1, 1]
+        );
+
+        ComplainOutputFormat::Rich
+            .render_file(
+                &PathBuf::from(file),
+                data,
+                &diagnostics,
+                4,
+                false,
+                &mut report,
+            )
+            .unwrap();
+
+        let report = String::from_utf8(report).unwrap();
+
+        assert!(report.contains(" rich.txt:1:4\n"));
+        assert!(report.contains(" abc·\n"));
+        assert!(report.contains(" rich.txt:2:1\n"));
+        assert!(report.contains("     abc\n"));
+        assert!(report.contains("^^^^"));
+
+        remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn rich_tab_width() {
+        let file = "rich_tab_width.txt";
+        let data = "a\tbc \n";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+        let mut report = Vec::new();
+
+        ac.ignore_tabs_within_lines();
+        ac.tab_width(8);
+        write(file, data).unwrap();
+
+        let diagnostics = ac.diagnose().unwrap();
+
+        ComplainOutputFormat::Rich
+            .render_file(
+                &PathBuf::from(file),
+                data,
+                &diagnostics,
+                8,
+                false,
+                &mut report,
+            )
+            .unwrap();
+
+        let report = String::from_utf8(report).unwrap();
+
+        let caret = report
+            .lines()
+            .find(|line| line.contains('^'))
+            .and_then(|line| line.split_once('|'))
+            .map(|(_, line)| line.trim_start_matches("\u{1b}[0m"))
+            .unwrap();
+
+        assert!(report.contains(" a       bc·\n"));
+        assert_eq!(caret.len() - caret.trim_start().len(), 11);
+
+        remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn default_trait() {
+        assert_eq!(
+            ComplainOutputFormat::default(),
+            ComplainOutputFormat::Human
+        );
+    }
+}
+
+mod fix {
+    use aeruginous::{Complain, ComplainOutputFormat, IndentationUnit};
+    use aeruginous_io::PathBufLikeReader;
+    use std::{
+        fs::{remove_file, write},
+        path::PathBuf,
+    };
+
+    macro_rules! make_test {
+        ( $( $name:ident : $i:ident , $text:literal -> $fixed:literal ),+ ) => {
+            $(
+                #[test]
+                fn $name() {
+                    let file = concat!(stringify!($name), ".txt");
+                    let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+                    ac.fix();
+                    ac.indent_by(IndentationUnit::$i);
+                    write(file, $text).unwrap();
+                    ac.process().unwrap();
+
+                    assert_eq!(file.read_silently().unwrap(), $fixed);
+
+                    remove_file(file).unwrap();
+                }
+            )+
+        };
+    }
+
+    make_test!(
+        fix_0001: Spaces, "abc" -> "abc\n",
+        fix_0002: Spaces, "abc\r\ndef\r\n" -> "abc\ndef\n",
+        fix_0004: Spaces, "abc \t\n" -> "abc\n",
+        fix_0005_1: Spaces, "\tabc\n\t\tdef\n" -> "    abc\n        def\n",
+        fix_0005_2: Tabs, "    abc\n        def\n" -> "\tabc\n\t\tdef\n",
+        fix_0005_3: Tabs, "      abc\n" -> "      abc\n",
+        fix_0006: Spaces, "  \tabc\n" -> "    abc\n",
+        fix_0007: Spaces, "a\tb\tc\n" -> "a   b   c\n",
+        fix_mercy: Spaces, "\
+#[aeruginous::mercy::0003::start]
+\tabc \t
+#[aeruginous::mercy::0003::end]
+\tabc \t
+" -> "\
+#[aeruginous::mercy::0003::start]
+\tabc \t
+#[aeruginous::mercy::0003::end]
+    abc
+",
+        fix_mercy_next: Spaces, "\
+#[aeruginous::mercy::0004::next]
+abc \t
+abc \t
+" -> "\
+#[aeruginous::mercy::0004::next]
+abc \t
+abc
+"
+    );
+
+    #[test]
+    fn unfixable_violations_remain() {
+        let file = "unfixable_violations_remain.txt";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        ac.fix();
+        write(file, format!("{} \n", "a".repeat(81))).unwrap();
+
+        assert_eq!(ac.process().unwrap(), 1);
+        assert_eq!(ac.diagnose().unwrap()[0].rule(), "ÆC-0003");
+
+        remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn stdin_requires_output_file() {
+        let mut ac = Complain::new(vec![PathBuf::from("-")]);
+
+        ac.fix();
+        ac.format(ComplainOutputFormat::Sarif);
+
+        assert_eq!(ac.process(), Err(sysexits::ExitCode::Usage));
+    }
+}
+
+mod baseline {
+    use aeruginous::Complain;
+    use aeruginous_io::PathBufLikeReader;
+    use std::{
+        fs::{remove_file, write},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn baseline() {
+        let file = "baseline.txt";
+        let baseline = "baseline.json";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        write(file, "abc \ndef \n").unwrap();
+        ac.write_baseline(baseline);
+
+        assert_eq!(ac.process().unwrap(), 0);
+
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        ac.baseline(baseline);
+        write(file, "new \nabc \ndef \n").unwrap();
+
+        let diagnostics = ac.diagnose().unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line(), 1);
+
+        write(file, "abc\ndef \n").unwrap();
+        ac.prune_baseline();
+
+        assert_eq!(ac.process().unwrap(), 0);
+        assert_eq!(baseline.read_silently().unwrap().matches(": 1").count(), 1);
+
+        write(file, "abc \ndef \n").unwrap();
+
+        assert_eq!(ac.process().unwrap(), 1);
+
+        remove_file(file).unwrap();
+        remove_file(baseline).unwrap();
+    }
+
+    #[test]
+    fn prune_subset() {
+        let files = ["baseline_subset_1.txt", "baseline_subset_2.txt"];
+        let baseline = "baseline_subset.json";
+        let mut ac = Complain::new(files.iter().map(PathBuf::from).collect());
+
+        write(files[0], "abc \n").unwrap();
+        write(files[1], "def \n").unwrap();
+        ac.write_baseline(baseline);
+
+        assert_eq!(ac.process().unwrap(), 0);
+
+        let mut ac = Complain::new(vec![PathBuf::from(files[0])]);
+
+        ac.baseline(baseline);
+        ac.prune_baseline();
+        write(files[0], "abc\n").unwrap();
+
+        assert_eq!(ac.process().unwrap(), 0);
+
+        let data = baseline.read_silently().unwrap();
+
+        assert!(!data.contains(files[0]));
+        assert!(data.contains(files[1]));
+
+        let mut ac = Complain::new(vec![PathBuf::from(files[1])]);
+
+        ac.baseline(baseline);
+
+        assert_eq!(ac.process().unwrap(), 0);
+
+        for file in files {
+            remove_file(file).unwrap();
+        }
+
+        remove_file(baseline).unwrap();
+    }
+}
+
+mod config {
+    use aeruginous::{Complain, ComplainSeverity, IndentationUnit};
+    use std::{
+        fs::{create_dir_all, read_to_string, remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn config_file() {
+        let dir = "config_file";
+        let mut ac = Complain::new(vec![PathBuf::from(dir)]);
+
+        create_dir_all(format!("{dir}/sub")).unwrap();
+        write(format!("{dir}/a.txt"), "\tabc \n").unwrap();
+        write(format!("{dir}/sub/b.txt"), "\tabc \n").unwrap();
+        write(
+            format!("{dir}/config.toml"),
+            "[[section]]\n\
+             files = [\"*.txt\"]\n\
+             ignore_trailing_white_space_characters = true\n\
+             \n\
+             [[section]]\n\
+             files = [\"sub/*.txt\"]\n\
+             indent_by = \"tabs\"\n",
+        )
+        .unwrap();
+        ac.config(format!("{dir}/config.toml"));
+        ac.ignore_editorconfig();
+
+        assert_eq!(ac.diagnose_file(format!("{dir}/a.txt")).unwrap().len(), 1);
+        assert_eq!(ac.diagnose_file(format!("{dir}/sub/b.txt")).unwrap(), []);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn custom_rules() {
+        let dir = "custom_rules";
+        let mut ac = Complain::new(vec![
+            PathBuf::from(format!("{dir}/a.rs")),
+            PathBuf::from(format!("{dir}/b.txt")),
+        ]);
+
+        create_dir_all(dir).unwrap();
+        write(
+            format!("{dir}/a.rs"),
+            "let a = dbg!(1); // TODO\n\
+             // TODO(#12)\n\
+             // #[aeruginous::mercy::no-dbg::next]\n\
+             dbg!(a);\n",
+        )
+        .unwrap();
+        write(format!("{dir}/b.txt"), "dbg!(a);\n").unwrap();
+        write(
+            format!("{dir}/config.toml"),
+            "[[rule]]\n\
+             files = [\"*.rs\"]\n\
+             id = \"no-dbg\"\n\
+             message = \"Debugging macro left over.\"\n\
+             regex = 'dbg!\\('\n\
+             \n\
+             [[rule]]\n\
+             colour = \"green\"\n\
+             id = \"todo-issue\"\n\
+             message = \"TODO without issue number.\"\n\
+             regex = 'TODO($|[^(])'\n\
+             severity = \"warning\"\n",
+        )
+        .unwrap();
+        ac.config(format!("{dir}/config.toml"));
+
+        assert_eq!(
+            ac.diagnose()
+                .unwrap()
+                .iter()
+                .map(|d| (
+                    d.line(),
+                    d.column(),
+                    d.rule().as_str(),
+                    d.severity()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 9, "no-dbg", ComplainSeverity::Error),
+                (1, 21, "todo-issue", ComplainSeverity::Warning)
+            ]
+        );
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn editorconfig() {
+        let dir = "editorconfig";
+        let ac = Complain::new(vec![PathBuf::from(dir)]);
+
+        create_dir_all(dir).unwrap();
+        write(format!("{dir}/a.txt"), "\tabc \n").unwrap();
+        write(
+            format!("{dir}/.editorconfig"),
+            "[*.txt]\nindent_style = tab\ntrim_trailing_whitespace = false\n",
+        )
+        .unwrap();
+
+        assert_eq!(ac.diagnose_file(format!("{dir}/a.txt")).unwrap(), []);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn editorconfig_precedence() {
+        let dir = "editorconfig_precedence";
+        let ac = Complain::new(vec![PathBuf::from(dir)]);
+
+        create_dir_all(format!("{dir}/inner")).unwrap();
+        write(format!("{dir}/inner/a.txt"), "\tabcdef\n").unwrap();
+        write(
+            format!("{dir}/.editorconfig"),
+            "[*]\nindent_size = 8\nindent_style = tab\nmax_line_length = 10\n",
+        )
+        .unwrap();
+        write(
+            format!("{dir}/inner/.editorconfig"),
+            "[*]\nindent_size = 2\n",
+        )
+        .unwrap();
+
+        assert_eq!(ac.diagnose_file(format!("{dir}/inner/a.txt")).unwrap(), []);
+
+        write(format!("{dir}/inner/.editorconfig"), "[*]\ntab_width = 4\n")
+            .unwrap();
+
+        assert_eq!(ac.diagnose_file(format!("{dir}/inner/a.txt")).unwrap(), []);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn editorconfig_explicit() {
+        let dir = "editorconfig_explicit";
+        let mut ac = Complain::new(vec![PathBuf::from(dir)]);
+
+        create_dir_all(dir).unwrap();
+        write(format!("{dir}/a.txt"), "\tabc\n").unwrap();
+        write(format!("{dir}/.editorconfig"), "[*]\nindent_style = tab\n")
+            .unwrap();
+
+        assert_eq!(ac.diagnose_file(format!("{dir}/a.txt")).unwrap(), []);
+
+        ac.indent_by(IndentationUnit::Spaces);
+
+        assert_eq!(ac.diagnose_file(format!("{dir}/a.txt")).unwrap().len(), 1);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn license_header() {
+        let dir = "license_header";
+        let year = chrono::Datelike::year(&chrono::Local::now());
+        let mut ac = Complain::new(vec![
+            PathBuf::from(format!("{dir}/a.rs")),
+            PathBuf::from(format!("{dir}/b.py")),
+            PathBuf::from(format!("{dir}/c.txt")),
+        ]);
+
+        create_dir_all(dir).unwrap();
+        write(
+            format!("{dir}/a.rs"),
+            "/*\n * Copyright 2020 Jane Doe\n */\n\nfn main() {}\n",
+        )
+        .unwrap();
+        write(format!("{dir}/b.py"), "#!/usr/bin/env python3\nx = 1\n")
+            .unwrap();
+        write(format!("{dir}/c.txt"), "abc\n").unwrap();
+        write(
+            format!("{dir}/config.toml"),
+            "[header]\n\
+             current_year = true\n\
+             holder = \"Jane Doe\"\n\
+             template = \"Copyright {year} {holder}\"\n\
+             \n\
+             [header.styles.rs]\n\
+             first = \"/*\"\n\
+             last = \" */\"\n\
+             prefix = \" * \"\n",
+        )
+        .unwrap();
+        ac.config(format!("{dir}/config.toml"));
+
+        assert_eq!(
+            ac.diagnose()
+                .unwrap()
+                .iter()
+                .map(|d| (d.line(), d.column(), d.rule().as_str()))
+                .collect::<Vec<_>>(),
+            vec![(2, 14, "ÆC-0014"), (2, 1, "ÆC-0014")]
+        );
+
+        ac.fix();
+
+        assert_eq!(ac.process().unwrap(), 0);
+        assert_eq!(
+            read_to_string(format!("{dir}/a.rs")).unwrap(),
+            format!(
+                "/*\n * Copyright 2020-{year} Jane Doe\n */\n\nfn main() {{}}\n"
+            )
+        );
+        assert_eq!(
+            read_to_string(format!("{dir}/b.py")).unwrap(),
+            format!(
+                "#!/usr/bin/env python3\n# Copyright {year} Jane Doe\n\nx = 1\n"
+            )
+        );
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn license_header_outdated_and_deviating() {
+        let dir = "license_header_outdated_and_deviating";
+        let file = format!("{dir}/a.rs");
+        let contents = "/*\n * Copyright 2020 Jane Doe\n\nfn main() {}\n";
+        let mut ac = Complain::new(vec![PathBuf::from(&file)]);
+
+        create_dir_all(dir).unwrap();
+        write(&file, contents).unwrap();
+        write(
+            format!("{dir}/config.toml"),
+            "[header]\n\
+             current_year = true\n\
+             holder = \"Jane Doe\"\n\
+             template = \"Copyright {year} {holder}\"\n\
+             \n\
+             [header.styles.rs]\n\
+             first = \"/*\"\n\
+             last = \" */\"\n\
+             prefix = \" * \"\n",
+        )
+        .unwrap();
+        ac.config(format!("{dir}/config.toml"));
+
+        assert_eq!(
+            ac.diagnose()
+                .unwrap()
+                .iter()
+                .map(|d| (d.line(), d.column(), d.rule().as_str()))
+                .collect::<Vec<_>>(),
+            vec![(3, 1, "ÆC-0014")]
+        );
+
+        ac.fix();
+
+        assert_eq!(ac.process().unwrap(), 1);
+        assert_eq!(read_to_string(&file).unwrap(), contents);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_config_file() {
+        let file = "invalid_config_file.toml";
+        let mut ac = Complain::new(vec![PathBuf::from("LICENSE")]);
+
+        write(file, "line_width = 100\n").unwrap();
+        ac.config(file);
+
+        assert!(ac.main().is_err());
+
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn invalid_custom_rule() {
+        let file = "invalid_custom_rule.toml";
+        let mut ac = Complain::new(vec![PathBuf::from("LICENSE")]);
+
+        write(
+            file,
+            "[[rule]]\nid = \"x\"\nmessage = \"\"\nregex = \"(\"\n",
+        )
+        .unwrap();
+        ac.config(file);
+
+        assert!(ac.main().is_err());
+
+        std::fs::remove_file(file).unwrap();
+    }
+}
+
+mod profiles {
+    use aeruginous::Complain;
+    use std::{
+        fs::{create_dir_all, read_to_string, remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    fn rules(ac: &Complain) -> Vec<(usize, String)> {
+        ac.diagnose()
+            .unwrap()
+            .iter()
+            .map(|d| (d.line(), d.rule().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn makefile() {
+        let dir = "profile_makefile";
+        let mut ac = Complain::new(vec![PathBuf::from(dir)]);
+
+        create_dir_all(dir).unwrap();
+        write(format!("{dir}/Makefile"), "all:\n\techo\n").unwrap();
+        write(format!("{dir}/rules.mk"), "all:\n    echo\n").unwrap();
+
+        assert_eq!(rules(&ac), [(2, "ÆC-0005".to_string())]);
+
+        ac.ignore_profiles();
+
+        assert_eq!(rules(&ac), [(2, "ÆC-0005".to_string())]);
+        assert_eq!(
+            ac.diagnose().unwrap()[0].file(),
+            &PathBuf::from(format!("{dir}/Makefile"))
+        );
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn markdown() {
+        let file = "profile_markdown.md";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        write(
+            file,
+            "Title\n\
+             # Heading\n\
+             a hard  \n\
+             break   \n\
+             \n\
+             [link]: https://example.org/a/very/long/path/to/some/document/\
+             which/exceeds/the/line/width\n\
+             \n\
+             ##No space\n\
+             \n\
+             ```\n\
+             # no heading\n\
+             ```\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules(&ac),
+            [
+                (4, "ÆC-0004".to_string()),
+                (2, "ÆC-0015".to_string()),
+                (2, "ÆC-0015".to_string()),
+                (8, "ÆC-0015".to_string())
+            ]
+        );
+
+        ac.fix();
+        ac.ignore_markdown_heading_spacing();
+
+        assert_eq!(ac.process().unwrap(), 0);
+        assert!(read_to_string(file).unwrap().contains("a hard  \nbreak\n"));
+
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn yaml() {
+        let file = "profile_yaml.yml";
+        let mut ac = Complain::new(vec![PathBuf::from(file)]);
+
+        write(file, "a:\n\tb: 1\n  \tc: 2\n").unwrap();
+
+        assert_eq!(
+            rules(&ac),
+            [(2, "ÆC-0016".to_string()), (3, "ÆC-0016".to_string())]
+        );
+
+        ac.fix();
+
+        assert_eq!(ac.process().unwrap(), 0);
+        assert_eq!(read_to_string(file).unwrap(), "a:\n    b: 1\n    c: 2\n");
+
+        std::fs::remove_file(file).unwrap();
+    }
+}
+
 mod indentation_unit {
     use aeruginous::IndentationUnit;
 
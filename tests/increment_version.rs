@@ -70,4 +70,478 @@ make_test!(@success success_major: Major);
 make_test!(@success success_minor: Minor);
 make_test!(@success success_patch: Patch);
 
+#[test]
+fn detect_old_version() {
+    let directory = "tests/detect_old_version/";
+    let manifest = format!("{directory}Cargo.toml");
+
+    std::fs::create_dir(directory).unwrap();
+    std::fs::copy("Cargo.toml", &manifest).unwrap();
+    std::fs::copy("CITATION.cff", format!("{directory}CITATION.cff")).unwrap();
+
+    let mut iv = aeruginous::IncrementVersion::new(
+        vec![manifest.clone().into()],
+        vec![format!("{directory}CITATION.cff").into()],
+        String::new(),
+        Some("aeruginous".to_string()),
+        aeruginous::VersionRange::Patch,
+    );
+
+    iv.detect_old_version();
+
+    assert!(iv.main().is_ok());
+
+    let mut version = <aeruginous::Version as std::str::FromStr>::from_str(
+        aeruginous::VERSION,
+    )
+    .unwrap();
+
+    assert!(std::fs::read_to_string(manifest)
+        .unwrap()
+        .contains(&format!(
+            "version = \"{}\"",
+            &version
+                .increment(aeruginous::VersionRange::Patch)
+                .to_string()[1..]
+        )));
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn pre_releases() {
+    let directory = "tests/pre_releases/";
+    let file = std::path::PathBuf::from(directory).join("version.txt");
+    let steps = [
+        ("1.2.3", Some("premajor"), None, "2.0.0-rc.1"),
+        ("2.0.0-rc.1", Some("prerelease"), None, "2.0.0-rc.2"),
+        (
+            "2.0.0-rc.2",
+            Some("prerelease"),
+            Some("beta"),
+            "2.0.0-beta.1",
+        ),
+        ("2.0.0-beta.1", Some("major"), None, "2.0.0"),
+        ("2.0.0", Some("preminor"), None, "2.1.0-rc.1"),
+        ("2.1.0-rc.1", Some("patch"), None, "2.1.0"),
+        ("2.1.0", Some("prerelease"), None, "2.1.1-rc.1"),
+        ("2.1.1-rc.1", Some("minor"), None, "2.2.0"),
+        ("2.2.0", None, None, "v3.0.0-alpha.2"),
+    ];
+
+    std::fs::create_dir(directory).unwrap();
+
+    for (old, range, preid, new) in steps {
+        let mut iv = aeruginous::IncrementVersion::new(
+            vec![file.clone()],
+            Vec::new(),
+            old.to_string(),
+            None,
+            aeruginous::VersionRange::Patch,
+        );
+
+        std::fs::write(&file, format!("{old}\n")).unwrap();
+
+        if let Some(range) = range {
+            iv.range(range.parse().unwrap());
+        } else {
+            iv.set(new);
+        }
+
+        if let Some(preid) = preid {
+            iv.preid(preid);
+        }
+
+        assert!(iv.main().is_ok());
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            format!("{}\n", new.trim_start_matches('v'))
+        );
+    }
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn workspace() {
+    let directory = "tests/workspace/";
+    let file = |name: &str| std::path::PathBuf::from(directory).join(name);
+
+    std::fs::create_dir_all(file("member")).unwrap();
+    std::fs::write(
+        file("Cargo.toml"),
+        "[workspace]\n\
+         members = [\"member\"]\n\
+         \n\
+         [workspace.package]\n\
+         version = \"1.2.3\"\n\
+         \n\
+         [workspace.dependencies]\n\
+         member = { path = \"member\", version = \"1.2\" }\n\
+         serde = \"1.2\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        file("member/Cargo.toml"),
+        "[package]\n\
+         name = \"member\"\n\
+         version.workspace = true\n\
+         \n\
+         [dev-dependencies.member]\n\
+         path = \".\"\n\
+         version = \"=1.2.3\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        file("Cargo.lock"),
+        "version = 4\n\
+         \n\
+         [[package]]\n\
+         name = \"member\"\n\
+         version = \"1.2.3\"\n",
+    )
+    .unwrap();
+
+    let mut iv = aeruginous::IncrementVersion::new(
+        vec![
+            file("Cargo.lock"),
+            file("Cargo.toml"),
+            file("member/Cargo.toml"),
+        ],
+        Vec::new(),
+        String::new(),
+        None,
+        aeruginous::VersionRange::Minor,
+    );
+
+    iv.changelog(file("CHANGELOG.ron"));
+    iv.detect_old_version();
+
+    assert!(iv.main().is_ok());
+
+    let manifest = std::fs::read_to_string(file("Cargo.toml")).unwrap();
+    let member = std::fs::read_to_string(file("member/Cargo.toml")).unwrap();
+
+    assert!(manifest.contains("version = \"1.3.0\""));
+    assert!(manifest.contains("version = \"1.3\" }"));
+    assert!(manifest.contains("serde = \"1.2\""));
+    assert!(member.contains("version.workspace = true"));
+    assert!(member.contains("version = \"=1.3.0\""));
+    assert!(std::fs::read_to_string(file("Cargo.lock"))
+        .unwrap()
+        .contains("version = \"1.3.0\""));
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn dry_run() {
+    let directory = "tests/dry_run/";
+    let file = std::path::PathBuf::from(directory).join("version.txt");
+    let increment = |check: bool| {
+        let mut iv = aeruginous::IncrementVersion::new(
+            vec![file.clone()],
+            Vec::new(),
+            "1.2.3".to_string(),
+            None,
+            aeruginous::VersionRange::Major,
+        );
+
+        if check {
+            iv.check();
+        } else {
+            iv.dry_run();
+        }
+
+        iv.main()
+    };
+
+    std::fs::create_dir(directory).unwrap();
+    std::fs::write(&file, "1.2.3\n").unwrap();
+
+    assert!(increment(false).is_ok());
+    assert_eq!(increment(true), Err(sysexits::ExitCode::DataErr));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "1.2.3\n");
+
+    std::fs::write(&file, "2.0.0\n").unwrap();
+
+    assert!(increment(true).is_ok());
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn manifests() {
+    let directory = "tests/manifests/";
+    let file = |name: &str| std::path::PathBuf::from(directory).join(name);
+    let manifests = [
+        (
+            "Chart.yaml",
+            "version: 1.2.3\ndependencies:\n  - version: 1.2.3\n",
+            "version: 1.2.4\ndependencies:\n  - version: 1.2.3\n",
+        ),
+        (
+            "demo.csproj",
+            "<Project><PropertyGroup><Version>1.2.3</Version></PropertyGroup>\
+             <PackageReference Version=\"1.2.3\" /></Project>\n",
+            "<Project><PropertyGroup><Version>1.2.4</Version></PropertyGroup>\
+             <PackageReference Version=\"1.2.3\" /></Project>\n",
+        ),
+        (
+            "package.json",
+            "{\"version\": \"1.2.3\", \"dependencies\": {\"a\": \"1.2.3\"}}\n",
+            "{\"version\": \"1.2.4\", \"dependencies\": {\"a\": \"1.2.3\"}}\n",
+        ),
+        (
+            "pom.xml",
+            "<project><parent><version>1.2.3</version></parent>\
+             <version>1.2.3</version></project>\n",
+            "<project><parent><version>1.2.3</version></parent>\
+             <version>1.2.4</version></project>\n",
+        ),
+        (
+            "pyproject.toml",
+            "[project]\nversion = \"1.2.3\"\ndependencies = [\"a==1.2.3\"]\n",
+            "[project]\nversion = \"1.2.4\"\ndependencies = [\"a==1.2.3\"]\n",
+        ),
+        (
+            "setup.cfg",
+            "[metadata]\nversion = 1.2.3\n[options]\nrequires = a==1.2.3\n",
+            "[metadata]\nversion = 1.2.4\n[options]\nrequires = a==1.2.3\n",
+        ),
+    ];
+
+    std::fs::create_dir(directory).unwrap();
+
+    for (name, before, _) in manifests {
+        std::fs::write(file(name), before).unwrap();
+    }
+
+    assert!(aeruginous::IncrementVersion::new(
+        manifests.iter().map(|(name, _, _)| file(name)).collect(),
+        Vec::new(),
+        "1.2.3".to_string(),
+        None,
+        aeruginous::VersionRange::Patch
+    )
+    .main()
+    .is_ok());
+
+    for (name, _, after) in manifests {
+        assert_eq!(std::fs::read_to_string(file(name)).unwrap(), after);
+    }
+
+    std::fs::write(file("package.json"), "{\"name\": \"demo\"}\n").unwrap();
+
+    assert_eq!(
+        aeruginous::IncrementVersion::new(
+            vec![file("package.json")],
+            Vec::new(),
+            "1.2.3".to_string(),
+            None,
+            aeruginous::VersionRange::Patch
+        )
+        .main(),
+        Err(sysexits::ExitCode::DataErr)
+    );
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn commit_and_tag() {
+    let directory = "tests/commit_and_tag/";
+    let file = |name: &str| std::path::PathBuf::from(directory).join(name);
+    let increment = |force: bool| {
+        let mut iv = aeruginous::IncrementVersion::new(
+            vec![file(".version")],
+            Vec::new(),
+            "1.2.3".to_string(),
+            None,
+            aeruginous::VersionRange::Minor,
+        );
+
+        iv.changelog(file("CHANGELOG.ron"));
+        iv.commit();
+        iv.message("Release {version}");
+        iv.tag();
+
+        if force {
+            iv.force();
+        }
+
+        iv.main()
+    };
+
+    std::fs::create_dir(directory).unwrap();
+    std::fs::write(file(".version"), "1.2.3\n").unwrap();
+    std::fs::write(file("README.md"), "# Example\n").unwrap();
+    std::fs::write(file("staged.txt"), "old\n").unwrap();
+    std::fs::write(
+        file("CHANGELOG.ron"),
+        "(references: {}, sections: [(\
+           references: {},\
+           version: (major: 1, minor: 3, patch: 0),\
+           released: \"2024-01-01T00:00:00Z\",\
+           introduction: Some(\"A feature release.\"),\
+           changes: (references: {}, changes: {\"Added\": [\"tags\"]}),\
+         )])",
+    )
+    .unwrap();
+
+    let repository = git2::Repository::init(directory).unwrap();
+    let mut config = repository.config().unwrap();
+
+    config.set_str("user.name", "Example").unwrap();
+    config.set_str("user.email", "example@example.com").unwrap();
+
+    let mut index = repository.index().unwrap();
+
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+
+    let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = repository.signature().unwrap();
+
+    repository
+        .commit(Some("HEAD"), &signature, &signature, "Init", &tree, &[])
+        .unwrap();
+    std::fs::write(file("README.md"), "# Changed\n").unwrap();
+    std::fs::write(file("staged.txt"), "new\n").unwrap();
+    index.add_path(std::path::Path::new("staged.txt")).unwrap();
+    index.write().unwrap();
+
+    assert_eq!(increment(false), Err(sysexits::ExitCode::DataErr));
+    assert_eq!(
+        std::fs::read_to_string(file(".version")).unwrap(),
+        "1.2.3\n"
+    );
+    assert!(increment(true).is_ok());
+    assert_eq!(
+        std::fs::read_to_string(file(".version")).unwrap(),
+        "1.3.0\n"
+    );
+
+    let head = repository.head().unwrap().peel_to_commit().unwrap();
+    let tag = repository
+        .revparse_single("v1.3.0")
+        .unwrap()
+        .peel_to_tag()
+        .unwrap();
+
+    assert_eq!(head.message(), Some("Release 1.3.0"));
+    assert_eq!(
+        tag.message(),
+        Some("A feature release.\n\n### Added\n\n- tags\n")
+    );
+    assert_eq!(tag.target_id(), head.id());
+    assert_eq!(
+        repository
+            .status_file(std::path::Path::new("README.md"))
+            .unwrap(),
+        git2::Status::WT_MODIFIED
+    );
+    assert_eq!(
+        repository
+            .status_file(std::path::Path::new("staged.txt"))
+            .unwrap(),
+        git2::Status::INDEX_MODIFIED
+    );
+    assert_eq!(
+        head.tree().unwrap().get_name("staged.txt").unwrap().id(),
+        tree.get_name("staged.txt").unwrap().id()
+    );
+
+    std::fs::write(file(".version"), "1.2.3\n").unwrap();
+
+    assert_eq!(increment(true), Err(sysexits::ExitCode::DataErr));
+    assert_eq!(
+        std::fs::read_to_string(file(".version")).unwrap(),
+        "1.2.3\n"
+    );
+    assert_eq!(
+        repository.head().unwrap().peel_to_commit().unwrap().id(),
+        head.id()
+    );
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn configured_locations() {
+    let directory = "tests/configured_locations/";
+    let file = |name: &str| std::path::PathBuf::from(directory).join(name);
+    let increment = || {
+        let mut iv = aeruginous::IncrementVersion::new(
+            Vec::new(),
+            Vec::new(),
+            "1.2.3".to_string(),
+            None,
+            aeruginous::VersionRange::Minor,
+        );
+
+        iv.config(file("incver.toml"));
+        iv.main()
+    };
+
+    std::fs::create_dir(directory).unwrap();
+    std::fs::write(
+        file("incver.toml"),
+        "[[file]]\n\
+         path = \"lib.rs\"\n\
+         search = 'VERSION: &str = \"v{version}\";'\n\
+         \n\
+         [[file]]\n\
+         path = \"Dockerfile\"\n\
+         search = \"LABEL version={major}.{minor}.{patch}\"\n\
+         replace = \"LABEL version={version} major={major}\"\n",
+    )
+    .unwrap();
+    std::fs::write(file("Dockerfile"), "LABEL version=1.2.3\n").unwrap();
+    std::fs::write(
+        file("lib.rs"),
+        "pub const VERSION: &str = \"v1.2.3\";\nconst A: &str = \"1.2.3\";\n",
+    )
+    .unwrap();
+
+    assert!(increment().is_ok());
+    assert_eq!(
+        std::fs::read_to_string(file("Dockerfile")).unwrap(),
+        "LABEL version=1.3.0 major=1\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(file("lib.rs")).unwrap(),
+        "pub const VERSION: &str = \"v1.3.0\";\nconst A: &str = \"1.2.3\";\n"
+    );
+    assert_eq!(increment(), Err(sysexits::ExitCode::DataErr));
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn disagreeing_sources() {
+    let directory = "tests/disagreeing_sources/";
+    let citation = format!("{directory}CITATION.cff");
+
+    std::fs::create_dir(directory).unwrap();
+    std::fs::copy("Cargo.toml", format!("{directory}Cargo.toml")).unwrap();
+    std::fs::write(&citation, "version: 0.0.1\n").unwrap();
+
+    let mut iv = aeruginous::IncrementVersion::new(
+        vec![format!("{directory}Cargo.toml").into(), citation.into()],
+        Vec::new(),
+        String::new(),
+        Some("aeruginous".to_string()),
+        aeruginous::VersionRange::Patch,
+    );
+
+    iv.detect_old_version();
+
+    assert_eq!(iv.main(), Err(sysexits::ExitCode::DataErr));
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
 /******************************************************************************/